@@ -0,0 +1,175 @@
+use crate::{score_cell, score_guess, words::ANSWERS, CharacterState, Mask, Shade};
+
+/// Plays the answer side of an adversarial game: there is no fixed answer,
+/// only the words that still fit every row so far. Each guess gets whichever
+/// feedback keeps the most of them alive, so the player has to corner it.
+#[derive(Clone, Debug)]
+pub struct Adversary {
+    candidates: Vec<&'static str>,
+}
+
+impl Default for Adversary {
+    fn default() -> Self {
+        Self {
+            candidates: ANSWERS.to_vec(),
+        }
+    }
+}
+
+impl Adversary {
+    /// A word that fits every row so far, used as the answer whenever one is
+    /// needed, e.g. to show it after a loss.
+    pub fn answer(&self) -> &'static str {
+        self.candidates[0]
+    }
+
+    /// Scores `guess` against every candidate and keeps the largest group
+    /// that got the same feedback. Masked cells are left out of the feedback,
+    /// so words that only differ there stay in the same group. Ties go to the
+    /// feedback that gives the least away, and never to a win while another
+    /// group is as big.
+    pub fn respond(&mut self, guess: &str, mask: &Mask, row_idx: usize) -> [CharacterState; 5] {
+        let groups = self.group_by(|candidate| score_guess(candidate, guess, mask, row_idx));
+        let Some((char_states, mut words)) = groups.into_iter().max_by_key(|(states, words)| {
+            let solved = words.len() == 1 && words[0] == guess;
+            (words.len(), !solved, std::cmp::Reverse(revealed(states)))
+        }) else {
+            // No candidates left only happens if the word list is empty.
+            return score_guess(guess, guess, mask, row_idx);
+        };
+
+        // The guess only wins once it is the last word standing.
+        if words.len() > 1 {
+            words.retain(|word| *word != guess);
+        }
+        self.candidates = words;
+        char_states
+    }
+
+    /// Decides the delayed feedback at `positions` of an earlier `guess`,
+    /// again keeping the largest group of candidates.
+    pub fn settle(&mut self, guess: &str, positions: &[usize]) {
+        self.keep_largest(|candidate| {
+            positions
+                .iter()
+                .map(|idx| score_cell(candidate, guess, *idx))
+                .collect::<Vec<_>>()
+        });
+    }
+
+    /// Settles whether `letter` is in the word for a hint.
+    pub fn commit_letter(&mut self, letter: char) {
+        self.keep_largest(|candidate| candidate.contains(letter));
+    }
+
+    /// Settles the letter at `column` for a hint.
+    pub fn commit_position(&mut self, column: usize) {
+        self.keep_largest(|candidate| candidate.chars().nth(column));
+    }
+
+    fn keep_largest<F, K>(&mut self, feedback: F)
+    where
+        F: Fn(&str) -> K,
+        K: PartialEq,
+    {
+        let groups = self.group_by(feedback);
+        if let Some((_, words)) = groups.into_iter().max_by_key(|(_, words)| words.len()) {
+            self.candidates = words;
+        }
+    }
+
+    /// Splits the candidates into groups that got the same feedback.
+    fn group_by<F, K>(&self, feedback: F) -> Vec<(K, Vec<&'static str>)>
+    where
+        F: Fn(&str) -> K,
+        K: PartialEq,
+    {
+        let mut groups: Vec<(K, Vec<&'static str>)> = Vec::new();
+        for candidate in &self.candidates {
+            let key = feedback(candidate);
+            match groups.iter_mut().find(|(other, _)| *other == key) {
+                Some((_, words)) => words.push(candidate),
+                None => groups.push((key, vec![candidate])),
+            }
+        }
+        groups
+    }
+}
+
+/// How much a feedback pattern tells the player, green counting double.
+fn revealed(char_states: &[CharacterState; 5]) -> usize {
+    char_states
+        .iter()
+        .map(|state| match state {
+            CharacterState::Correct | CharacterState::LetterHidden(Shade::Correct) => 2,
+            CharacterState::WrongPlace
+            | CharacterState::InWord
+            | CharacterState::LetterHidden(Shade::WrongPlace) => 1,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adversary(candidates: &[&'static str]) -> Adversary {
+        Adversary {
+            candidates: candidates.to_vec(),
+        }
+    }
+
+    #[test]
+    fn keeps_the_largest_group() {
+        let mut adversary = adversary(&["crane", "ghost", "crave", "craze"]);
+        let char_states = adversary.respond("ghost", &Mask::parse(".....").unwrap(), 0);
+        assert_eq!(char_states, [CharacterState::NotInWord; 5]);
+        assert_eq!(adversary.candidates, ["crane", "crave", "craze"]);
+    }
+
+    #[test]
+    fn refuses_a_win_while_another_group_is_as_big() {
+        let mask = Mask::parse(".....").unwrap();
+        let mut adversary = adversary(&["ghost", "crane"]);
+        let char_states = adversary.respond("ghost", &mask, 0);
+        assert_ne!(char_states, [CharacterState::Correct; 5]);
+        assert_eq!(adversary.candidates, ["crane"]);
+
+        let char_states = adversary.respond("crane", &mask, 1);
+        assert_eq!(char_states, [CharacterState::Correct; 5]);
+    }
+
+    #[test]
+    fn candidates_fit_every_row_shown() {
+        let mask = Mask::parse("#~?*./.~#?./~~~~~/.....").unwrap();
+        let mut adversary = Adversary::default();
+        let mut shown: Vec<(&str, usize, [CharacterState; 5])> = Vec::new();
+        let mut settled = Vec::new();
+
+        for (row_idx, guess) in ["crane", "moist", "plumb", "dowdy"].into_iter().enumerate() {
+            let char_states = adversary.respond(guess, &mask, row_idx);
+            shown.push((guess, row_idx, char_states));
+            if let Some(previous_idx) = row_idx.checked_sub(1) {
+                let (previous, _, previous_states) = shown[previous_idx];
+                let delayed = (0..5)
+                    .filter(|idx| previous_states[*idx] == CharacterState::Delayed)
+                    .collect::<Vec<_>>();
+                adversary.settle(previous, &delayed);
+                for idx in delayed {
+                    settled.push((previous, idx, score_cell(adversary.answer(), previous, idx)));
+                }
+            }
+
+            assert!(!adversary.candidates.is_empty());
+            for candidate in &adversary.candidates {
+                for (guess, row_idx, char_states) in &shown {
+                    assert_eq!(score_guess(candidate, guess, &mask, *row_idx), *char_states);
+                }
+                for (guess, idx, char_state) in &settled {
+                    assert_eq!(score_cell(candidate, guess, *idx), *char_state, "{candidate}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{score_guess, solver::Solver, words::ANSWERS, Mask, MAX_GUESSES};
+use rand::{rngs::StdRng, SeedableRng};
+use std::fmt;
+
+/// Which mask every benchmark game is played with.
+pub enum MaskChoice {
+    Fixed(Mask),
+    /// A fresh random mask per game, hiding each cell with this probability.
+    Random(f64),
+}
+
+pub struct BenchOptions {
+    pub mask: MaskChoice,
+    pub seed: Option<u64>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            mask: MaskChoice::Fixed(Mask::default()),
+            seed: None,
+        }
+    }
+}
+
+pub struct BenchReport {
+    mask: String,
+    /// `solved_in[n]` is the number of games solved with `n + 1` guesses.
+    solved_in: [usize; MAX_GUESSES],
+    failures: usize,
+}
+
+impl BenchReport {
+    fn games(&self) -> usize {
+        self.solved_in.iter().sum::<usize>() + self.failures
+    }
+
+    fn solved(&self) -> usize {
+        self.games() - self.failures
+    }
+
+    fn average_guesses(&self) -> Option<f64> {
+        let total_guesses = self
+            .solved_in
+            .iter()
+            .enumerate()
+            .map(|(idx, games)| (idx + 1) * games)
+            .sum::<usize>();
+
+        match self.solved() {
+            0 => None,
+            solved => Some(total_guesses as f64 / solved as f64),
+        }
+    }
+
+    fn failure_rate(&self) -> f64 {
+        match self.games() {
+            0 => 0.0,
+            games => self.failures as f64 / games as f64,
+        }
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Mask:            {}", self.mask)?;
+        writeln!(f, "Games:           {}", self.games())?;
+        match self.average_guesses() {
+            Some(average) => writeln!(f, "Average guesses: {average:.2}")?,
+            None => writeln!(f, "Average guesses: -")?,
+        }
+        writeln!(
+            f,
+            "Failure rate:    {:.1}% ({} lost)",
+            self.failure_rate() * 100.0,
+            self.failures
+        )?;
+        writeln!(f)?;
+
+        let widest = self.solved_in.iter().chain([&self.failures]).max().copied();
+        let bar = |count: usize| match widest {
+            Some(widest) if widest > 0 => "#".repeat((count * 40).div_ceil(widest)),
+            _ => String::new(),
+        };
+        for (idx, count) in self.solved_in.iter().enumerate() {
+            writeln!(f, "{} | {:<40} {count}", idx + 1, bar(*count))?;
+        }
+        write!(f, "X | {:<40} {}", bar(self.failures), self.failures)
+    }
+}
+
+/// Plays the built-in solver against every answer in the word list.
+pub fn run(options: &BenchOptions) -> BenchReport {
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut report = BenchReport {
+        mask: match &options.mask {
+            MaskChoice::Fixed(mask) => mask.to_string(),
+            MaskChoice::Random(density) => format!("random ({:.0}% hidden)", density * 100.0),
+        },
+        solved_in: [0; MAX_GUESSES],
+        failures: 0,
+    };
+
+    for answer in ANSWERS {
+        let mask = match &options.mask {
+            MaskChoice::Fixed(mask) => mask.clone(),
+            MaskChoice::Random(density) => Mask::random(&mut rng, *density, MAX_GUESSES),
+        };
+
+        match play(answer, &mask) {
+            Some(guesses) => report.solved_in[guesses - 1] += 1,
+            None => report.failures += 1,
+        }
+    }
+
+    report
+}
+
+/// Plays one game and returns how many guesses it took, or `None` if the
+/// solver ran out of rows.
+fn play(answer: &str, mask: &Mask) -> Option<usize> {
+    let mut solver = Solver::new(ANSWERS);
+
+    for row_idx in 0..MAX_GUESSES {
+        let guess = solver.next_guess()?;
+        if guess == answer {
+            return Some(row_idx + 1);
+        }
+
+        let char_states = score_guess(answer, guess, mask, row_idx);
+        solver.apply(guess, &char_states, mask, row_idx);
+    }
+
+    None
+}
@@ -0,0 +1,242 @@
+//! A newline-delimited JSON protocol on stdin and stdout, so bots written in
+//! any language can play against the real scoring rules.
+//!
+//! The game opens with a `new_game` message describing the board. The bot
+//! answers with `{"type": "guess", "word": "crane"}` and gets a `result` with
+//! the feedback of every cell, or an `error`. At any time the bot can send
+//! `{"type": "new_game"}` to start over, or `{"type": "quit"}`.
+
+use crate::{
+    handle_key, keys::KeyBindings, words, App, CellKind, CharacterState, GameState, Mask, COLUMNS,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+/// How every game of the session is set up.
+pub struct BotOptions {
+    /// Plays this answer every game instead of a random one.
+    pub answer: Option<String>,
+    pub mask: Mask,
+    pub adversarial: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    Guess { word: String },
+    NewGame,
+    Quit,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Response {
+    NewGame {
+        rows: usize,
+        columns: usize,
+        /// The kind of every cell, row by row.
+        mask: Vec<Vec<CellKind>>,
+    },
+    Result {
+        row: usize,
+        char_states: [CharacterState; 5],
+        /// Earlier rows whose delayed cells were filled in by this guess.
+        updates: Vec<RowUpdate>,
+        state: Outcome,
+        /// Set once the game is over.
+        answer: Option<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Serialize)]
+struct RowUpdate {
+    row: usize,
+    char_states: [CharacterState; 5],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Outcome {
+    InProgress,
+    Won,
+    Lost,
+}
+
+pub fn run<R: BufRead, W: Write>(options: &BotOptions, input: R, mut output: W) -> io::Result<()> {
+    let mut app = new_game(options);
+    send(&mut output, &describe(&app))?;
+
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request>(&line) {
+            Err(err) => error(format!("can't read message: {err}")),
+            Ok(Request::Quit) => return Ok(()),
+            Ok(Request::NewGame) => {
+                app = new_game(options);
+                describe(&app)
+            }
+            Ok(Request::Guess { .. }) if app.state != GameState::InProgress => {
+                error("the game is over, send new_game to play again")
+            }
+            Ok(Request::Guess { word }) => guess(&mut app, &word),
+        };
+        send(&mut output, &response)?;
+    }
+
+    Ok(())
+}
+
+fn new_game(options: &BotOptions) -> App {
+    let mut app = if options.adversarial {
+        App::adversarial(options.mask.clone())
+    } else {
+        let answer = options.answer.as_deref().unwrap_or_else(|| words::random_answer());
+        App::new(answer, options.mask.clone())
+    };
+    // Guesses are played with the default keys, whatever the settings say.
+    app.key_bindings = KeyBindings::default();
+    app
+}
+
+fn describe(app: &App) -> Response {
+    let rows = app.guesses.len();
+    Response::NewGame {
+        rows,
+        columns: COLUMNS,
+        mask: (0..rows)
+            .map(|row_idx| (0..COLUMNS).map(|idx| app.mask.get_mask(row_idx, idx)).collect())
+            .collect(),
+    }
+}
+
+/// Plays `word` through the same key handling as the terminal UI.
+fn guess(app: &mut App, word: &str) -> Response {
+    if word.len() != 5 || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+        return error(format!("'{word}' is not a five letter word"));
+    }
+
+    let before = app.guesses.clone();
+    for key in word.chars().map(KeyCode::Char).chain([KeyCode::Enter]) {
+        let _ = handle_key(app, KeyEvent::new(key, KeyModifiers::NONE));
+    }
+
+    let row = app.current_guess - 1;
+    let updates = (0..row)
+        .filter(|row_idx| app.guesses[*row_idx].char_states != before[*row_idx].char_states)
+        .map(|row_idx| RowUpdate {
+            row: row_idx,
+            char_states: app.guesses[row_idx].char_states,
+        })
+        .collect();
+    let (state, answer) = match &app.state {
+        GameState::InProgress => (Outcome::InProgress, None),
+        GameState::Won => (Outcome::Won, Some(app.correct_word.clone())),
+        GameState::Lost(answer) => (Outcome::Lost, Some(answer.clone())),
+    };
+    Response::Result {
+        row,
+        char_states: app.guesses[row].char_states,
+        updates,
+        state,
+        answer,
+    }
+}
+
+fn error(message: impl Into<String>) -> Response {
+    Response::Error {
+        message: message.into(),
+    }
+}
+
+fn send<W: Write>(output: &mut W, response: &Response) -> io::Result<()> {
+    serde_json::to_writer(&mut *output, response)?;
+    writeln!(output)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAX_GUESSES;
+    use serde_json::{json, Value};
+    use std::io::Cursor;
+
+    /// Plays `guesses` against "crane" and returns every reply after the
+    /// opening `new_game`.
+    fn play(mask: &str, guesses: &[&str]) -> Vec<Value> {
+        let options = BotOptions {
+            answer: Some(String::from("crane")),
+            mask: Mask::parse(mask).unwrap(),
+            adversarial: false,
+        };
+        let script = guesses
+            .iter()
+            .map(|word| format!("{}\n", json!({"type": "guess", "word": word})))
+            .collect::<String>();
+        let mut output = Vec::new();
+        run(&options, Cursor::new(script), &mut output).unwrap();
+        let mut replies = output
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice::<Value>(line).unwrap());
+        assert_eq!(replies.next().unwrap()["type"], "new_game");
+        replies.collect()
+    }
+
+    #[test]
+    fn an_invalid_word_is_an_error() {
+        let replies = play("...../.....", &["cat", "cr4ne"]);
+        assert_eq!(replies.len(), 2);
+        for reply in replies {
+            assert_eq!(reply["type"], "error");
+            assert!(reply["message"].as_str().unwrap().contains("five letter"), "{reply}");
+        }
+    }
+
+    #[test]
+    fn the_answer_wins() {
+        let replies = play("...../.....", &["crane"]);
+        assert_eq!(replies[0]["type"], "result");
+        assert_eq!(replies[0]["row"], 0);
+        assert_eq!(replies[0]["char_states"], Value::from(vec!["correct"; 5]));
+        assert_eq!(replies[0]["state"], "won");
+        assert_eq!(replies[0]["answer"], "crane");
+    }
+
+    #[test]
+    fn running_out_of_guesses_loses() {
+        let words = ["fight", "moist", "plumb", "jazzy", "dowdy", "crane"];
+        let replies = play("...../.....", &words);
+        assert_eq!(replies[3]["state"], "in_progress");
+        assert_eq!(replies[3]["answer"], Value::Null);
+        assert_eq!(replies[4]["row"], MAX_GUESSES - 1);
+        assert_eq!(replies[4]["state"], "lost");
+        assert_eq!(replies[4]["answer"], "crane");
+        assert_eq!(replies[5]["type"], "error");
+    }
+
+    #[test]
+    fn delayed_cells_update_the_earlier_row() {
+        let replies = play("~~.../...../.....", &["cloud", "fight"]);
+        assert_eq!(
+            replies[0]["char_states"],
+            json!(["delayed", "delayed", "not_in_word", "not_in_word", "not_in_word"])
+        );
+        assert_eq!(replies[0]["updates"], json!([]));
+        assert_eq!(
+            replies[1]["updates"],
+            json!([{
+                "row": 0,
+                "char_states": ["correct", "not_in_word", "not_in_word", "not_in_word", "not_in_word"],
+            }])
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{
+    bench::{BenchOptions, MaskChoice},
+    history::HistoryOptions,
+    lan::{HostOptions, JoinOptions, DEFAULT_PORT},
+    mask_editor::EditorOptions,
+    multi::{MultiOptions, BOARD_COUNTS},
+    puzzle_code::{self, Puzzle},
+    replay::ReplayOptions,
+    timer::TimerMode,
+    Mask,
+};
+use std::{path::PathBuf, time::Duration};
+
+pub const USAGE: &str = "\
+Usage:
+    spotle_tui [OPTIONS]               Play a game in the terminal
+    spotle_tui bench [OPTIONS]         Play the built-in solver against every answer
+    spotle_tui replay <FILE> [OPTIONS] Play back a recorded game
+    spotle_tui hotseat                 Race a friend on the same keyboard, taking turns
+    spotle_tui endless                 Solve puzzles back to back until the first loss
+    spotle_tui multi [--boards <N>]    Play 2, 4 or 8 boards at once [default: 4]
+    spotle_tui host [OPTIONS]          Host a race over the network
+    spotle_tui join <ADDR> [OPTIONS]   Join a race hosted at ADDR, e.g. 10.0.0.7:4815
+    spotle_tui code <ANSWER> [--mask <MASK>]
+                                       Print a shareable code for a custom puzzle
+    spotle_tui stats                   Show your statistics and endless high scores
+    spotle_tui history [--mode <MODE>] Browse past games, only those of MODE if given
+    spotle_tui edit-mask [NAME]        Draw a mask and save it under NAME
+    spotle_tui tutorial                Play the guided first game again
+    spotle_tui keys                    List the key bindings
+
+Play options:
+    --code <CODE>       Play the custom puzzle shared as CODE
+    --mask <MASK>       Mask to play with: `default`, a cell pattern such as
+                        `..#../.?.../.#~../..#../..*..` or a saved mask name.
+                        Cells are `.` visible, `#` hidden, `?` binary (in the
+                        word or not), `~` delayed by a row, `*` letter hidden
+    --adversarial       Play against an answer that changes to dodge your guesses
+    --markers           Mark cells and keys with = right place, + in the word,
+                        - not in the word and # masked, besides their colors.
+                        On by default when NO_COLOR is set, or always with
+                        \"markers\": true in settings.json in the data directory
+    --plain             Play line by line on stdin and stdout instead of the
+                        full screen UI, e.g. with a screen reader or a script
+    --bot               Speak newline-delimited JSON on stdin and stdout, for
+                        bots. Every game uses the answer of --code if given,
+                        otherwise a random one. Send {\"type\": \"guess\",
+                        \"word\": \"crane\"}, {\"type\": \"new_game\"} or
+                        {\"type\": \"quit\"}
+    --record <FILE>     Record every key press of the game to FILE
+    --timed             Show a timer that starts on the first key press
+    --speedrun          Like --timed, and record solve times in the stats
+    --countdown <SECS>  Lose if the game is not solved within SECS seconds
+    --guess-time <SECS> Lose if a guess is not submitted within SECS seconds
+
+Bench options:
+    --mask <MASK>       Like the play option, or `random` for a new random
+                        mask every game
+    --density <P>       Chance of hiding a cell with `--mask random` [default: 0.2]
+    --seed <N>          Seed for random masks
+
+Replay options:
+    --speed <X>         Playback speed multiplier [default: 1]
+
+Host and join options:
+    --port <PORT>       Port to host on [default: 4815]
+    --name <NAME>       Name shown to the other players [default: $USER]
+
+Game keys: ? shows help, tab buys a hint with your last row, ctrl-w or ctrl-u
+clears the word, ctrl-z suspends, ctrl-c or ctrl-q quits, and once the game is
+over r switches between the board as played and its masked cells revealed.
+Keys can be rebound with \"key_bindings\" in settings.json, e.g.
+{\"f1\": \"help\", \"ctrl+w\": null}, see `spotle_tui keys` for the actions.
+A game cut short by a signal or a crash is picked up again the next time
+spotle_tui runs without play options.
+Colors: \"theme\" in settings.json picks `dark`, `light`, `classic` or
+`high_contrast`. Colors are brought down to what COLORTERM and TERM say the
+terminal supports, or to \"color_depth\": `ansi16`, `ansi256` or `true_color`.
+Cells are drawn as big as the terminal allows, from one character up to
+letters in a block font, or always at \"cell_size\": `small`, `medium` or
+`large`.
+History keys: up and down select, enter opens the game's board, m cycles
+the mode shown.
+Replay keys: space pauses, n or → steps one key, + and - change the speed.
+Mask editor keys: arrows move, space or a click cycles a cell, r randomizes,
+p previews the solver against a word, s saves.
+";
+
+pub enum Command {
+    Play(PlayOptions),
+    Bench(BenchOptions),
+    Replay(ReplayOptions),
+    Hotseat,
+    Endless,
+    Multi(MultiOptions),
+    Code(Puzzle),
+    Stats,
+    History(HistoryOptions),
+    Host(HostOptions),
+    Join(JoinOptions),
+    EditMask(EditorOptions),
+    Tutorial,
+    Keys,
+    Help,
+}
+
+#[derive(Default)]
+pub struct PlayOptions {
+    pub puzzle: Option<Puzzle>,
+    pub mask: Option<Mask>,
+    pub record: Option<PathBuf>,
+    pub timer: Option<TimerMode>,
+    pub adversarial: bool,
+    pub markers: bool,
+    pub plain: bool,
+    pub bot: bool,
+}
+
+pub fn parse<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.peekable();
+    match args.peek().map(String::as_str) {
+        None => Ok(Command::Play(PlayOptions::default())),
+        Some(arg) if arg.starts_with("--") && arg != "--help" => {
+            parse_play(args).map(Command::Play)
+        }
+        _ => match args.next().as_deref() {
+            Some("bench") => parse_bench(args).map(Command::Bench),
+            Some("replay") => parse_replay(args).map(Command::Replay),
+            Some("hotseat") => match args.next() {
+                None => Ok(Command::Hotseat),
+                Some(arg) => Err(format!("unknown hotseat option '{arg}'")),
+            },
+            Some("endless") => Ok(Command::Endless),
+            Some("multi") => parse_multi(args).map(Command::Multi),
+            Some("code") => parse_code(args).map(Command::Code),
+            Some("stats") => Ok(Command::Stats),
+            Some("history") => parse_history(args).map(Command::History),
+            Some("host") => parse_host(args).map(Command::Host),
+            Some("join") => parse_join(args).map(Command::Join),
+            Some("tutorial") => Ok(Command::Tutorial),
+            Some("keys") => Ok(Command::Keys),
+            Some("edit-mask") => parse_edit_mask(args).map(Command::EditMask),
+            Some("-h" | "--help" | "help") => Ok(Command::Help),
+            Some(other) => Err(format!("unknown command '{other}'")),
+            None => unreachable!("peeked an argument above"),
+        },
+    }
+}
+
+fn parse_play<I: Iterator<Item = String>>(mut args: I) -> Result<PlayOptions, String> {
+    let mut options = PlayOptions::default();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{arg}'"));
+        match arg.as_str() {
+            "--code" => {
+                let code = value()?;
+                let puzzle = puzzle_code::decode(&code)
+                    .map_err(|err| format!("can't read puzzle code '{code}': {err}"))?;
+                options.puzzle = Some(puzzle);
+            }
+            "--mask" => options.mask = Some(Mask::from_arg(&value()?)?),
+            "--record" => options.record = Some(value()?.into()),
+            "--adversarial" => options.adversarial = true,
+            "--markers" => options.markers = true,
+            "--plain" => options.plain = true,
+            "--bot" => options.bot = true,
+            "--timed" => options.timer = Some(TimerMode::Stopwatch),
+            "--speedrun" => options.timer = Some(TimerMode::Speedrun),
+            "--countdown" => options.timer = Some(TimerMode::GameLimit(parse_seconds(&value()?)?)),
+            "--guess-time" => {
+                options.timer = Some(TimerMode::GuessLimit(parse_seconds(&value()?)?));
+            }
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|seconds: &f64| *seconds > 0.0)
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or(format!("'{value}' is not a positive number of seconds"))
+}
+
+fn parse_bench<I: Iterator<Item = String>>(mut args: I) -> Result<BenchOptions, String> {
+    let mut options = BenchOptions::default();
+    let mut random_masks = false;
+    let mut density = 0.2;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{arg}'"));
+        match arg.as_str() {
+            "--mask" => match value()?.as_str() {
+                "random" => random_masks = true,
+                arg => {
+                    random_masks = false;
+                    options.mask = MaskChoice::Fixed(Mask::from_arg(arg)?);
+                }
+            },
+            "--density" => {
+                density = value()?
+                    .parse()
+                    .ok()
+                    .filter(|density| (0.0..=1.0).contains(density))
+                    .ok_or("density must be a number between 0 and 1")?;
+            }
+            "--seed" => {
+                options.seed = Some(value()?.parse().map_err(|_| "seed must be a number")?);
+            }
+            _ => return Err(format!("unknown bench option '{arg}'")),
+        }
+    }
+
+    if random_masks {
+        options.mask = MaskChoice::Random(density);
+    }
+
+    Ok(options)
+}
+
+fn parse_replay<I: Iterator<Item = String>>(mut args: I) -> Result<ReplayOptions, String> {
+    let path = args.next().ok_or("missing replay file")?;
+    let mut options = ReplayOptions {
+        path: path.into(),
+        speed: 1.0,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{arg}'"));
+        match arg.as_str() {
+            "--speed" => {
+                options.speed = value()?
+                    .parse()
+                    .ok()
+                    .filter(|speed: &f64| *speed > 0.0)
+                    .ok_or("speed must be a positive number")?;
+            }
+            _ => return Err(format!("unknown replay option '{arg}'")),
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_history<I: Iterator<Item = String>>(mut args: I) -> Result<HistoryOptions, String> {
+    let mut options = HistoryOptions { mode: None };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{arg}'"));
+        match arg.as_str() {
+            "--mode" => options.mode = Some(value()?),
+            _ => return Err(format!("unknown history option '{arg}'")),
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_code<I: Iterator<Item = String>>(mut args: I) -> Result<Puzzle, String> {
+    let answer = args.next().ok_or("missing answer for the puzzle")?;
+    let mut mask = Mask::default();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{arg}'"));
+        match arg.as_str() {
+            "--mask" => mask = Mask::from_arg(&value()?)?,
+            _ => return Err(format!("unknown code option '{arg}'")),
+        }
+    }
+
+    Puzzle::new(&answer, mask)
+}
+
+fn parse_multi<I: Iterator<Item = String>>(mut args: I) -> Result<MultiOptions, String> {
+    let mut options = MultiOptions { boards: 4 };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{arg}'"));
+        match arg.as_str() {
+            "--boards" => {
+                options.boards = value()?
+                    .parse()
+                    .ok()
+                    .filter(|boards| BOARD_COUNTS.contains(boards))
+                    .ok_or("boards must be 2, 4 or 8")?;
+            }
+            _ => return Err(format!("unknown multi option '{arg}'")),
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_edit_mask<I: Iterator<Item = String>>(mut args: I) -> Result<EditorOptions, String> {
+    let name = args.next();
+    if let Some(name) = &name {
+        Mask::path(name)?;
+    }
+    match args.next() {
+        None => Ok(EditorOptions { name }),
+        Some(arg) => Err(format!("unknown edit-mask option '{arg}'")),
+    }
+}
+
+fn default_name() -> String {
+    std::env::var("USER").unwrap_or_else(|_| String::from("player"))
+}
+
+fn parse_host<I: Iterator<Item = String>>(mut args: I) -> Result<HostOptions, String> {
+    let mut options = HostOptions {
+        port: DEFAULT_PORT,
+        name: default_name(),
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{arg}'"));
+        match arg.as_str() {
+            "--port" => options.port = value()?.parse().map_err(|_| "port must be a number")?,
+            "--name" => options.name = value()?,
+            _ => return Err(format!("unknown host option '{arg}'")),
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_join<I: Iterator<Item = String>>(mut args: I) -> Result<JoinOptions, String> {
+    let addr = args.next().ok_or("missing address to join")?;
+    let mut options = JoinOptions {
+        addr: if addr.contains(':') {
+            addr
+        } else {
+            format!("{addr}:{DEFAULT_PORT}")
+        },
+        name: default_name(),
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{arg}'"));
+        match arg.as_str() {
+            "--name" => options.name = value()?,
+            _ => return Err(format!("unknown join option '{arg}'")),
+        }
+    }
+
+    Ok(options)
+}
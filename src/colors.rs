@@ -0,0 +1,158 @@
+//! How many colors the terminal can show, and bringing theme colors down to
+//! what it can.
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use tui::style::Color;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorDepth {
+    /// The 16 named ANSI colors, whose exact shades the terminal picks.
+    Ansi16,
+    /// The xterm palette of 256 indexed colors.
+    Ansi256,
+    /// Any 24-bit RGB color.
+    TrueColor,
+}
+
+/// The steps of the 6x6x6 color cube in the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorDepth {
+    /// Reads `COLORTERM`, then the terminfo name in `TERM`, which by
+    /// convention ends in `-256color` or `-direct` when it has more than the
+    /// basic colors.
+    pub fn detect() -> Self {
+        let colorterm = env::var("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+        let term = env::var("TERM").unwrap_or_default().to_ascii_lowercase();
+        if term.ends_with("-direct") || term.contains("truecolor") || term.contains("24bit") {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// `color` as close as this depth can show it. Named colors are left
+    /// alone, every terminal has them.
+    pub fn convert(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed(r, g, b)),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_named(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(index)) => {
+                let (r, g, b) = indexed_rgb(index);
+                nearest_named(r, g, b)
+            }
+            _ => color,
+        }
+    }
+}
+
+/// The closest entry of the color cube or the gray ramp, 16 to 255. The
+/// first 16 entries are left out since terminals change them.
+fn nearest_indexed(r: u8, g: u8, b: u8) -> u8 {
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|idx| CUBE_LEVELS[*idx].abs_diff(value))
+            .unwrap_or(0) as u8
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+    [cube, gray]
+        .into_iter()
+        .min_by_key(|index| distance((r, g, b), indexed_rgb(*index)))
+        .unwrap_or(cube)
+}
+
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16_RGB[index as usize],
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+/// xterm's default shades of the first 16 palette entries.
+const ANSI16_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The named color of the same hue, or a gray for colors with little of
+/// one. Going by plain RGB distance would turn the muted greens and yellows
+/// of most palettes gray, which loses what the color was saying.
+fn nearest_named(r: u8, g: u8, b: u8) -> Color {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    if chroma < 48 {
+        return match max {
+            0..=39 => Color::Black,
+            40..=159 => Color::DarkGray,
+            160..=223 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+
+    let (r, g, b, chroma) = (r as i32, g as i32, b as i32, chroma as i32);
+    let hue = if max as i32 == r {
+        60 * (g - b) / chroma
+    } else if max as i32 == g {
+        120 + 60 * (b - r) / chroma
+    } else {
+        240 + 60 * (r - g) / chroma
+    };
+    let bright = max > 230 && min > 80;
+    let sector = (hue.rem_euclid(360) + 30) / 60 % 6;
+    let hues = match bright {
+        false => [Color::Red, Color::Yellow, Color::Green, Color::Cyan, Color::Blue, Color::Magenta],
+        true => [
+            Color::LightRed,
+            Color::LightYellow,
+            Color::LightGreen,
+            Color::LightCyan,
+            Color::LightBlue,
+            Color::LightMagenta,
+        ],
+    };
+    hues[sector as usize]
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
@@ -0,0 +1,204 @@
+use crate::{
+    draw_game, handle_key, history, key_presses, read_event,
+    status_bar::{self, StatusBar},
+    storage, words, App, GameState, Mask, MAX_GUESSES,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::Paragraph,
+    Frame, Terminal,
+};
+
+const HIGH_SCORES_FILE: &str = "high_scores.json";
+const HIGH_SCORES_KEPT: usize = 10;
+const POINTS_PER_SOLVE: u32 = 10;
+const POINTS_PER_UNUSED_GUESS: u32 = 5;
+const MASK_DENSITY: f64 = 0.2;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: u32,
+    pub solved: u32,
+    /// Seconds since the Unix epoch when the run ended.
+    pub ended_at: u64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighScores {
+    pub scores: Vec<HighScore>,
+}
+
+impl HighScores {
+    pub fn load() -> Self {
+        storage::load(HIGH_SCORES_FILE)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        storage::save(HIGH_SCORES_FILE, self)
+    }
+
+    /// Adds a finished run to the table and returns its rank, starting at 1,
+    /// or `None` if it did not make the table.
+    pub fn insert(&mut self, high_score: HighScore) -> Option<usize> {
+        let rank = self
+            .scores
+            .iter()
+            .position(|other| high_score.score > other.score)
+            .unwrap_or(self.scores.len());
+        self.scores.insert(rank, high_score);
+        self.scores.truncate(HIGH_SCORES_KEPT);
+        (rank < HIGH_SCORES_KEPT).then_some(rank + 1)
+    }
+}
+
+impl fmt::Display for HighScores {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Endless high scores:")?;
+        if self.scores.is_empty() {
+            return writeln!(f, "  none yet");
+        }
+        for (idx, high_score) in self.scores.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>3}. {:>5} pts  {} solved",
+                idx + 1,
+                high_score.score,
+                high_score.solved
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A chain of puzzles that only ends on the first loss.
+struct Run {
+    app: App,
+    solved: u32,
+    score: u32,
+    last_result: Option<String>,
+    rank: Option<usize>,
+}
+
+impl Run {
+    fn new() -> Self {
+        let mut run = Self {
+            app: next_puzzle(),
+            solved: 0,
+            score: 0,
+            last_result: None,
+            rank: None,
+        };
+        run.update_title();
+        run
+    }
+
+    fn update_title(&mut self) {
+        self.app.title = format!("Endless - {} pts", self.score);
+    }
+
+    /// Scores a solved puzzle and moves straight on to the next one.
+    fn solved(&mut self) {
+        // Rows spent on hints don't count as unused.
+        let unused_guesses = (self.app.playable_rows() - self.app.current_guess) as u32;
+        let points = POINTS_PER_SOLVE + unused_guesses * POINTS_PER_UNUSED_GUESS;
+        self.solved += 1;
+        self.score += points;
+        self.last_result = Some(format!(
+            "Solved '{}' in {} (+{points})",
+            self.app.correct_word, self.app.current_guess
+        ));
+        // Like the high scores, the log is not worth stopping the run over.
+        let _ = history::record(&self.app);
+        self.app = next_puzzle();
+        self.update_title();
+    }
+
+    fn lost(&mut self) {
+        let _ = history::record(&self.app);
+        let ended_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        let mut high_scores = HighScores::load();
+        self.rank = high_scores.insert(HighScore {
+            score: self.score,
+            solved: self.solved,
+            ended_at,
+        });
+        // A high score that could not be written is not worth ending the run
+        // screen over.
+        let _ = high_scores.save();
+    }
+
+    fn status(&self) -> String {
+        if self.app.state != GameState::InProgress {
+            let rank = match self.rank {
+                Some(rank) => format!(", #{rank} on the high score table"),
+                None => String::new(),
+            };
+            return format!(
+                "Run over: {} pts from {} puzzles{rank}",
+                self.score, self.solved
+            );
+        }
+
+        match &self.last_result {
+            Some(last_result) => format!("Puzzle {}. {last_result}", self.solved + 1),
+            None => String::from("Puzzle 1. Solve as many as you can, one loss ends the run."),
+        }
+    }
+}
+
+fn next_puzzle() -> App {
+    let mask = Mask::random(&mut rand::thread_rng(), MASK_DENSITY, MAX_GUESSES);
+    App {
+        mode: "Endless",
+        ..App::new(words::random_answer(), mask)
+    }
+}
+
+pub fn run<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+    let mut run = Run::new();
+
+    loop {
+        terminal.draw(|f| ui(f, &mut run))?;
+
+        for key in key_presses(&run.app, read_event(terminal, &run.app.key_bindings)?) {
+            let was_in_progress = run.app.state == GameState::InProgress;
+            if handle_key(&mut run.app, key).is_break() {
+                return Ok(());
+            }
+
+            match run.app.state {
+                GameState::Won => run.solved(),
+                GameState::Lost(_) if was_in_progress => run.lost(),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn ui<B: Backend>(frame: &mut Frame<B>, run: &mut Run) {
+    let (area, status_area) = status_bar::split(frame.size());
+    draw_game(frame, &mut run.app, area);
+    if let Some(status_area) = status_area {
+        let bar = StatusBar::for_game(&run.app)
+            .info(format!("Puzzle {}", run.solved + 1))
+            .info(format!("{} pts", run.score));
+        status_bar::draw(frame, &run.app.theme, &bar, status_area);
+    }
+
+    if area.height > 0 {
+        let status = Paragraph::new(run.status())
+            .alignment(Alignment::Center)
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        frame.render_widget(status, Rect::new(area.x, area.bottom() - 1, area.width, 1));
+    }
+}
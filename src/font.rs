@@ -0,0 +1,52 @@
+//! A block font for the letters of large cells.
+
+pub const WIDTH: usize = 5;
+pub const HEIGHT: usize = 5;
+const FILL: char = '█';
+
+/// Every glyph is [`WIDTH`] characters wide, `#` marks the filled ones.
+const LETTERS: [[&str; HEIGHT]; 26] = [
+    [" ### ", "#   #", "#####", "#   #", "#   #"],
+    ["#### ", "#   #", "#### ", "#   #", "#### "],
+    [" ####", "#    ", "#    ", "#    ", " ####"],
+    ["#### ", "#   #", "#   #", "#   #", "#### "],
+    ["#####", "#    ", "#### ", "#    ", "#####"],
+    ["#####", "#    ", "#### ", "#    ", "#    "],
+    [" ####", "#    ", "#  ##", "#   #", " ####"],
+    ["#   #", "#   #", "#####", "#   #", "#   #"],
+    ["#####", "  #  ", "  #  ", "  #  ", "#####"],
+    ["#####", "   # ", "   # ", "#  # ", " ##  "],
+    ["#   #", "#  # ", "###  ", "#  # ", "#   #"],
+    ["#    ", "#    ", "#    ", "#    ", "#####"],
+    ["#   #", "## ##", "# # #", "#   #", "#   #"],
+    ["#   #", "##  #", "# # #", "#  ##", "#   #"],
+    [" ### ", "#   #", "#   #", "#   #", " ### "],
+    ["#### ", "#   #", "#### ", "#    ", "#    "],
+    [" ### ", "#   #", "# # #", "#  # ", " ## #"],
+    ["#### ", "#   #", "#### ", "#  # ", "#   #"],
+    [" ####", "#    ", " ### ", "    #", "#### "],
+    ["#####", "  #  ", "  #  ", "  #  ", "  #  "],
+    ["#   #", "#   #", "#   #", "#   #", " ### "],
+    ["#   #", "#   #", "#   #", " # # ", "  #  "],
+    ["#   #", "#   #", "# # #", "## ##", "#   #"],
+    ["#   #", " # # ", "  #  ", " # # ", "#   #"],
+    ["#   #", " # # ", "  #  ", "  #  ", "  #  "],
+    ["#####", "   # ", "  #  ", " #   ", "#####"],
+];
+
+/// The mask symbols that show up in cells, see [`crate::CellKind::symbol`].
+const SYMBOLS: [(char, [&str; HEIGHT]); 4] = [
+    ('#', [" # # ", "#####", " # # ", "#####", " # # "]),
+    ('?', [" ### ", "#   #", "  ## ", "     ", "  #  "]),
+    ('~', ["     ", " #   ", "# # #", "   # ", "     "]),
+    ('*', ["     ", "# # #", " ### ", "# # #", "     "]),
+];
+
+/// The lines drawing `c`, or `None` for characters the font doesn't have.
+pub fn glyph(c: char) -> Option<Vec<String>> {
+    let lines = match c.to_ascii_lowercase() {
+        letter @ 'a'..='z' => &LETTERS[letter as usize - 'a' as usize],
+        symbol => &SYMBOLS.iter().find(|(other, _)| *other == symbol)?.1,
+    };
+    Some(lines.iter().map(|line| line.replace('#', &FILL.to_string())).collect())
+}
@@ -0,0 +1,189 @@
+//! The `?` overlay explaining colors, masks and keys, with example cells drawn
+//! the same way as on the board.
+
+use crate::{
+    clip, guessed_cell, hint_cell,
+    keys::{Action, KeyBindings},
+    CellKind, CellSize, CharacterState, Shade, Theme, CELL_HEIGHT, CELL_WIDTH,
+};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+const WIDTH: u16 = 66;
+const HEIGHT: u16 = 18;
+
+const INTRO: &str = "Guess the five letter word. Every guess colors its cells:";
+
+/// Example rows, each a word with the feedback of every cell, and what the
+/// feedback means.
+const EXAMPLES: [(&str, [CharacterState; 5], [&str; 3]); 2] = [
+    (
+        "world",
+        [
+            CharacterState::Correct,
+            CharacterState::WrongPlace,
+            CharacterState::NotInWord,
+            CharacterState::NotInWord,
+            CharacterState::Correct,
+        ],
+        [
+            "green: right letter, right place",
+            "yellow: in the word, somewhere else",
+            "grey: not in the word at all",
+        ],
+    ),
+    (
+        "light",
+        [
+            CharacterState::Masked,
+            CharacterState::InWord,
+            CharacterState::Delayed,
+            CharacterState::LetterHidden(Shade::Correct),
+            CharacterState::NotInWord,
+        ],
+        [
+            "reversed: masked, no feedback at all",
+            "rounded: only in the word or not",
+            "double: waits a row, *: letter hidden",
+        ],
+    ),
+];
+
+/// What the keys do, naming the ones the player has bound. Unbound actions
+/// are left out.
+fn keys(key_bindings: &KeyBindings) -> String {
+    let mut keys = vec![String::from("Type letters")];
+    for (action, what) in [
+        (Action::Submit, "submits"),
+        (Action::DeleteLetter, "deletes"),
+        (Action::Hint, "buys a hint with your last row"),
+        (Action::Back, "quits"),
+    ] {
+        if let Some(key) = key_bindings.key_for(action) {
+            keys.push(format!("{key} {what}"));
+        }
+    }
+    format!(
+        "Empty rows show the mask ahead: reversed cells are masked, ? binary, ~ delayed \
+         and * letter hidden. {}. After the game, r reveals what masked cells hid.",
+        keys.join(", ")
+    )
+}
+
+pub fn draw<B: Backend>(
+    frame: &mut Frame<B>,
+    theme: &Theme,
+    key_bindings: &KeyBindings,
+    area: Rect,
+) {
+    let width = WIDTH.min(area.width);
+    let height = HEIGHT.min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().fg(theme.border_color))
+        .title("Help - press any key to close");
+    let inner = block.inner(popup);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(block, popup);
+
+    // Sections that don't fit a short terminal are left out.
+    let clip = |rect: Rect| clip(rect, inner);
+    if let Some(area) = clip(Rect::new(inner.x + 1, inner.y, inner.width, 1)) {
+        frame.render_widget(Paragraph::new(INTRO), area);
+    }
+
+    let row_width = (CELL_WIDTH * 5) as u16;
+    let mut top = inner.y + 1;
+    for (word, char_states, legend) in EXAMPLES {
+        for (idx, (letter, state)) in word.chars().zip(char_states).enumerate() {
+            let cell = Rect::new(
+                inner.x + 1 + (idx * CELL_WIDTH) as u16,
+                top,
+                CELL_WIDTH as u16,
+                CELL_HEIGHT as u16,
+            );
+            if let Some(cell) = clip(cell) {
+                frame.render_widget(guessed_cell(theme, CellSize::Small, letter, state), cell);
+            }
+        }
+
+        let legend = legend
+            .iter()
+            .map(|line| Spans::from(Span::raw(*line)))
+            .collect::<Vec<_>>();
+        let legend_area = Rect::new(
+            inner.x + row_width + 3,
+            top,
+            inner.width.saturating_sub(row_width + 3),
+            CELL_HEIGHT as u16,
+        );
+        if let Some(legend_area) = clip(legend_area) {
+            frame.render_widget(Paragraph::new(legend), legend_area);
+        }
+        top += CELL_HEIGHT as u16;
+    }
+
+    let hint = Rect::new(inner.x + 1, top, CELL_WIDTH as u16, CELL_HEIGHT as u16);
+    if let Some(hint) = clip(hint) {
+        let cell = hint_cell(CellSize::Small, CellKind::Hidden.symbol(), theme.hint_color, None);
+        frame.render_widget(cell, hint);
+    }
+    let hint_legend = Rect::new(
+        hint.right() + 2,
+        top + 1,
+        inner.width.saturating_sub(CELL_WIDTH as u16 + 3),
+        1,
+    );
+    if let Some(hint_legend) = clip(hint_legend) {
+        frame.render_widget(
+            Paragraph::new("a row spent on a hint, here one that unmasked a cell"),
+            hint_legend,
+        );
+    }
+    top += CELL_HEIGHT as u16;
+
+    let keys = Paragraph::new(keys(key_bindings))
+        .wrap(Wrap { trim: true })
+        .style(Style::default().add_modifier(Modifier::DIM));
+    let keys_area = Rect::new(
+        inner.x + 1,
+        top,
+        inner.width.saturating_sub(2),
+        inner.bottom().saturating_sub(top),
+    );
+    if let Some(keys_area) = clip(keys_area) {
+        frame.render_widget(keys, keys_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn draws_on_a_short_terminal() {
+        let key_bindings = KeyBindings::default();
+        let mut terminal = Terminal::new(TestBackend::new(80, 10)).unwrap();
+        terminal
+            .draw(|f| draw(f, &Theme::default(), &key_bindings, f.size()))
+            .unwrap();
+        let screen = crate::tests::screen(&terminal);
+        assert!(screen.contains("Help - press any key to close"), "{screen}");
+        assert!(screen.contains(INTRO), "{screen}");
+        assert!(screen.contains("green: right letter"), "{screen}");
+    }
+}
@@ -0,0 +1,147 @@
+//! Hints the player can buy during a game. Every hint costs the last unused
+//! row of the board, which then shows what was bought.
+
+use crate::{
+    keys::{Action, Key},
+    toast::Severity::{self, Error},
+    App, CellKind, CharacterState,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use serde::{Deserialize, Serialize};
+
+/// A hint that was bought, kept on the row it was paid with.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Hint {
+    /// Made a masked cell of the row being guessed visible.
+    Unmask { column: usize },
+    /// Told whether `letter` is somewhere in the word.
+    Letter { letter: char, in_word: bool },
+    /// Revealed the letter in one position of the answer.
+    Position { column: usize, letter: char },
+}
+
+/// The steps of choosing a hint, opened with tab.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HintMenu {
+    Choose,
+    /// Waiting for the column, 1 to 5, of the cell to unmask.
+    Unmask,
+    /// Waiting for the letter to test.
+    Letter,
+}
+
+impl HintMenu {
+    /// What the menu asks for, and the key that cancels it if one is bound.
+    pub fn prompt(self, cancel: Option<Key>) -> String {
+        let prompt = match self {
+            HintMenu::Choose => "Hint for one row: 1 unmask a cell, 2 test a letter, 3 reveal a position",
+            HintMenu::Unmask => "Unmask which cell of this row? 1-5",
+            HintMenu::Letter => "Test which letter? a-z",
+        };
+        match cancel {
+            Some(key) => format!("{prompt}, {key} cancel"),
+            None => prompt.to_string(),
+        }
+    }
+}
+
+/// Opens the hint menu, if a hint can be paid for.
+pub fn open(app: &mut App) {
+    if app.playable_rows() > app.current_guess + 1 {
+        app.hint_menu = Some(HintMenu::Choose);
+    } else {
+        app.toasts.push(Error, "No spare row to pay for a hint");
+    }
+}
+
+/// Handles a key press while the hint menu is open.
+pub fn handle_key(app: &mut App, menu: HintMenu, key: KeyEvent) {
+    app.hint_menu = None;
+    let hint = match (menu, key.code) {
+        _ if app.key_bindings.action(key) == Some(Action::Back) => return,
+        (HintMenu::Choose, KeyCode::Char('1')) => {
+            app.hint_menu = Some(HintMenu::Unmask);
+            return;
+        }
+        (HintMenu::Choose, KeyCode::Char('2')) => {
+            app.hint_menu = Some(HintMenu::Letter);
+            return;
+        }
+        (HintMenu::Choose, KeyCode::Char('3')) => match reveal_position(app) {
+            Some(hint) => hint,
+            None => {
+                app.toasts.push(Severity::Info, "Every position is already solved");
+                return;
+            }
+        },
+        (HintMenu::Unmask, KeyCode::Char(digit @ '1'..='5')) => {
+            let column = digit as usize - '1' as usize;
+            if app.mask.get_mask(app.current_guess, column) == CellKind::Visible {
+                app.toasts.push(Error, "That cell isn't masked");
+                return;
+            }
+            unmask(app, column)
+        }
+        (HintMenu::Letter, KeyCode::Char(letter)) if letter.is_ascii_alphabetic() => {
+            test_letter(app, letter.to_ascii_lowercase())
+        }
+        // Anything else leaves the menu where it was.
+        _ => {
+            app.hint_menu = Some(menu);
+            return;
+        }
+    };
+
+    // Pay with the last row that is still free.
+    let row_idx = app.playable_rows() - 1;
+    app.guesses[row_idx].hint = Some(hint);
+}
+
+fn unmask(app: &mut App, column: usize) -> Hint {
+    let row_idx = app.current_guess;
+    if let Some(cell) = app.mask.items.get_mut(row_idx * 5 + column) {
+        *cell = CellKind::Visible;
+    }
+    app.guesses[row_idx].char_states[column] = CharacterState::Unknown;
+    Hint::Unmask { column }
+}
+
+fn test_letter(app: &mut App, letter: char) -> Hint {
+    if let Some(adversary) = app.adversary.as_mut() {
+        adversary.commit_letter(letter);
+        app.correct_word = adversary.answer().to_string();
+    }
+
+    let in_word = app.correct_word.contains(letter);
+    if app.get_letter_state(letter) == CharacterState::Unknown {
+        let state = if in_word {
+            CharacterState::WrongPlace
+        } else {
+            CharacterState::NotInWord
+        };
+        app.set_letter_state(letter, state);
+    }
+    Hint::Letter { letter, in_word }
+}
+
+/// Reveals the first position no earlier row got right, if there is one.
+fn reveal_position(app: &mut App) -> Option<Hint> {
+    let known = |column: usize| {
+        app.guesses.iter().any(|row| {
+            row.char_states[column] == CharacterState::Correct
+                || matches!(row.hint, Some(Hint::Position { column: other, .. }) if other == column)
+        })
+    };
+    let column = (0..5).find(|column| !known(*column))?;
+
+    if let Some(adversary) = app.adversary.as_mut() {
+        adversary.commit_position(column);
+        app.correct_word = adversary.answer().to_string();
+    }
+
+    let letter = app.correct_word.chars().nth(column)?;
+    app.set_letter_state(letter, CharacterState::Correct);
+    Some(Hint::Position { column, letter })
+}
+
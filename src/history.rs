@@ -0,0 +1,318 @@
+//! Every finished game, one JSON line each, and the screen that lists them
+//! and opens any of them as a read-only board.
+
+use crate::{
+    draw_game,
+    keys::{Action, KeyBindings},
+    read_event,
+    settings::Settings,
+    status_bar::{self, StatusBar},
+    storage, App, GameState, Mask, Row, Theme,
+};
+use crossterm::event::{Event, KeyCode};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tui::{
+    backend::Backend,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame, Terminal,
+};
+
+const HISTORY_FILE: &str = "history.jsonl";
+const COLUMNS_HEADER: &str = "Ended (UTC)       Mode           Answer  Guesses  Result  Time   Mask";
+
+pub struct HistoryOptions {
+    /// Only list games of this mode, matched without case.
+    pub mode: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// Seconds since the Unix epoch when the game ended.
+    pub ended_at: u64,
+    pub mode: String,
+    pub answer: String,
+    /// The mask as a cell pattern, see [`Mask::parse`].
+    pub mask: String,
+    pub guesses: usize,
+    pub won: bool,
+    /// How long the game took, for games played with a timer.
+    pub duration_ms: Option<u64>,
+    /// The board as it ended, hint rows included.
+    pub rows: Vec<Row>,
+}
+
+impl Entry {
+    fn new(app: &App) -> Self {
+        let ended_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        Self {
+            ended_at,
+            mode: app.mode.to_string(),
+            answer: app.correct_word.clone(),
+            mask: app.mask.to_string(),
+            guesses: app.current_guess,
+            won: app.state == GameState::Won,
+            duration_ms: app.timer.as_ref().map(|timer| timer.elapsed().as_millis() as u64),
+            rows: app.guesses.clone(),
+        }
+    }
+
+    /// The game as it ended, to be looked at but not played.
+    fn board(&self) -> App {
+        let mask = Mask::parse(&self.mask).unwrap_or_default();
+        let mut app = App {
+            title: format_time(self.ended_at),
+            mode: "History",
+            ..App::with_guesses(&self.answer, mask, self.rows.len())
+        };
+        app.guesses = self.rows.clone();
+        app.current_guess = self.guesses.min(self.rows.len());
+        for row in &self.rows[..app.current_guess] {
+            app.update_key_status(row);
+        }
+        app.state = match self.won {
+            true => GameState::Won,
+            false => GameState::Lost(self.answer.clone()),
+        };
+        app
+    }
+
+    fn summary(&self) -> String {
+        let result = if self.won { "won" } else { "lost" };
+        let time = self.duration_ms.map_or(String::from("-"), |ms| {
+            let secs = ms / 1000;
+            format!("{}:{:02}", secs / 60, secs % 60)
+        });
+        let mask = match self.mask == Mask::default().to_string() {
+            true => "default",
+            false => &self.mask,
+        };
+        format!(
+            "{}  {:<13}  {:<6}  {:>7}  {:<6}  {:>5}  {mask}",
+            format_time(self.ended_at),
+            self.mode,
+            self.answer,
+            format!("{}/{}", self.guesses, self.rows.len()),
+            result,
+            time,
+        )
+    }
+}
+
+/// Adds a finished game to the end of the log.
+pub fn record(app: &App) -> io::Result<()> {
+    let dir = storage::data_dir().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "could not find a data directory")
+    })?;
+    fs::create_dir_all(&dir)?;
+
+    let mut line = serde_json::to_string(&Entry::new(app))?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(HISTORY_FILE))?
+        .write_all(line.as_bytes())
+}
+
+/// Every game in the log, oldest first. Lines that can't be read, like one
+/// cut short by a crash, are skipped.
+pub fn load() -> Vec<Entry> {
+    storage::data_dir()
+        .and_then(|dir| fs::read_to_string(dir.join(HISTORY_FILE)).ok())
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `secs` since the Unix epoch as a UTC date and time, like `2024-03-09 14:05`.
+fn format_time(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let (hour, minute) = (secs % 86_400 / 3600, secs % 3600 / 60);
+
+    // Howard Hinnant's civil_from_days.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year}-{month:02}-{day:02} {hour:02}:{minute:02}")
+}
+
+struct Screen {
+    /// Newest first.
+    entries: Vec<Entry>,
+    /// The modes that appear in the log, to cycle the filter through.
+    modes: Vec<String>,
+    filter: Option<String>,
+    selected: usize,
+    /// The entry being looked at, if one was opened.
+    board: Option<App>,
+    theme: Theme,
+    key_bindings: KeyBindings,
+}
+
+impl Screen {
+    fn visible(&self) -> Vec<&Entry> {
+        self.entries
+            .iter()
+            .filter(|entry| match &self.filter {
+                Some(mode) => entry.mode.eq_ignore_ascii_case(mode),
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Moves the filter on to the next mode, and back to every mode after
+    /// the last one.
+    fn next_filter(&mut self) {
+        let next = match &self.filter {
+            None => 0,
+            Some(mode) => match self.modes.iter().position(|other| other.eq_ignore_ascii_case(mode)) {
+                Some(idx) => idx + 1,
+                None => self.modes.len(),
+            },
+        };
+        self.filter = self.modes.get(next).cloned();
+        self.selected = 0;
+    }
+}
+
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, options: HistoryOptions) -> io::Result<()> {
+    let mut entries = load();
+    entries.reverse();
+    let mut modes = Vec::<String>::new();
+    for entry in &entries {
+        if !modes.contains(&entry.mode) {
+            modes.push(entry.mode.clone());
+        }
+    }
+    let settings = Settings::load();
+    let mut screen = Screen {
+        entries,
+        modes,
+        filter: options.mode,
+        selected: 0,
+        board: None,
+        theme: Theme::detect(&settings),
+        key_bindings: KeyBindings::with_overrides(&settings.key_bindings).0,
+    };
+
+    loop {
+        terminal.draw(|f| ui(f, &mut screen))?;
+
+        let Event::Key(key) = read_event(terminal, &screen.key_bindings)? else {
+            continue;
+        };
+        let action = screen.key_bindings.action(key);
+        if action == Some(Action::Quit) {
+            return Ok(());
+        }
+
+        if let Some(board) = screen.board.as_mut() {
+            match key.code {
+                KeyCode::Char('r') if board.has_masked_feedback() => {
+                    board.reveal_masked = !board.reveal_masked;
+                }
+                _ if action == Some(Action::Back) => screen.board = None,
+                KeyCode::Char('q') => screen.board = None,
+                _ => {}
+            }
+            continue;
+        }
+
+        let count = screen.visible().len();
+        match key.code {
+            _ if action == Some(Action::Back) => return Ok(()),
+            KeyCode::Char('q') => return Ok(()),
+            KeyCode::Up | KeyCode::Char('k') => screen.selected = screen.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                screen.selected = (screen.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Char('m') => screen.next_filter(),
+            _ if action == Some(Action::Submit) => {
+                screen.board = screen.visible().get(screen.selected).map(|entry| entry.board());
+            }
+            _ => {}
+        }
+    }
+}
+
+fn ui<B: Backend>(frame: &mut Frame<B>, screen: &mut Screen) {
+    let (area, status_area) = status_bar::split(frame.size());
+
+    let mode = screen
+        .visible()
+        .get(screen.selected)
+        .map(|entry| entry.mode.clone())
+        .unwrap_or_default();
+    if let Some(board) = screen.board.as_mut() {
+        draw_game(frame, board, area);
+        if let Some(status_area) = status_area {
+            let mut bar = StatusBar::new(board.mode);
+            if board.has_masked_feedback() {
+                bar = bar.key("r", "reveal");
+            }
+            let bar = bar.key("esc", "back").info(mode).info(board.correct_word.clone());
+            status_bar::draw(frame, &board.theme, &bar, status_area);
+        }
+        return;
+    }
+
+    let visible = screen.visible();
+    let title = match &screen.filter {
+        Some(mode) => format!("History - {mode}"),
+        None => String::from("History - all modes"),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut lines = vec![Spans::from(Span::styled(
+        COLUMNS_HEADER,
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    if visible.is_empty() {
+        lines.push(Spans::from("No games yet."));
+    }
+    // Keeps the selected entry in view, below the column names.
+    let rows = inner.height.saturating_sub(1) as usize;
+    let first = screen.selected.saturating_sub(rows.saturating_sub(1));
+    for (idx, entry) in visible.iter().enumerate().skip(first).take(rows) {
+        let style = match idx == screen.selected {
+            true => Style::default().add_modifier(Modifier::REVERSED),
+            false => Style::default(),
+        };
+        lines.push(Spans::from(Span::styled(entry.summary(), style)));
+    }
+    frame.render_widget(Paragraph::new(lines), inner);
+
+    if let Some(status_area) = status_area {
+        let bar = StatusBar::new("History")
+            .key("↑/↓", "select")
+            .key("enter", "open")
+            .key("m", "mode")
+            .key("q", "quit")
+            .progress(format!("{} games", visible.len()));
+        status_bar::draw(frame, &screen.theme, &bar, status_area);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    draw_game, exit_prompt, handle_key, key_presses,
+    keys::Action,
+    read_event,
+    status_bar::{self, StatusBar},
+    words, App, GameState, Mask,
+};
+use crossterm::event::KeyCode;
+use std::io;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::Paragraph,
+    Frame, Terminal,
+};
+
+/// Two players racing to solve the same puzzle on one keyboard. They take
+/// turns, one guess each, until somebody solves it or both run out of rows.
+struct Race {
+    players: [App; 2],
+    turn: usize,
+}
+
+impl Race {
+    fn new() -> Self {
+        let answer = words::random_answer();
+        let mask = Mask::default();
+        let player = |mask| App {
+            mode: "Hotseat",
+            ..App::new(answer, mask)
+        };
+        let mut race = Self {
+            players: [player(mask.clone()), player(mask)],
+            turn: 0,
+        };
+        race.update_titles();
+        race
+    }
+
+    fn winner(&self) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|player| player.state == GameState::Won)
+    }
+
+    fn is_over(&self) -> bool {
+        self.winner().is_some()
+            || self
+                .players
+                .iter()
+                .all(|player| player.state != GameState::InProgress)
+    }
+
+    /// Hands the turn to the other player once the current one has
+    /// submitted a guess, unless they have no rows left.
+    fn end_turn(&mut self) {
+        let other = 1 - self.turn;
+        if self.players[other].state == GameState::InProgress {
+            self.turn = other;
+        }
+        self.update_titles();
+    }
+
+    fn update_titles(&mut self) {
+        let over = self.is_over();
+        for (idx, player) in self.players.iter_mut().enumerate() {
+            player.title = if idx == self.turn && !over {
+                format!("Player {} - your turn", idx + 1)
+            } else {
+                format!("Player {}", idx + 1)
+            };
+        }
+    }
+
+    fn banner(&self) -> Option<String> {
+        match self.winner() {
+            Some(winner) => Some(format!(
+                "Player {} wins! {}",
+                winner + 1,
+                exit_prompt(&self.players[0].key_bindings)
+            )),
+            None if self.is_over() => Some(format!(
+                "Nobody got it! The answer was '{}'. {}",
+                self.players[0].correct_word,
+                exit_prompt(&self.players[0].key_bindings)
+            )),
+            None => None,
+        }
+    }
+}
+
+pub fn run<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+    let mut race = Race::new();
+
+    loop {
+        terminal.draw(|f| ui(f, &mut race))?;
+
+        for key in key_presses(
+            &race.players[race.turn],
+            read_event(terminal, &race.players[race.turn].key_bindings)?,
+        ) {
+            if race.is_over() {
+                let action = race.players[0].key_bindings.action(key);
+                if key.code == KeyCode::Char('q')
+                    || matches!(action, Some(Action::Quit | Action::Back))
+                {
+                    return Ok(());
+                }
+                continue;
+            }
+
+            let player = &mut race.players[race.turn];
+            let guesses_before = player.current_guess;
+            if handle_key(player, key).is_break() {
+                return Ok(());
+            }
+            if player.current_guess != guesses_before {
+                race.end_turn();
+            }
+        }
+    }
+}
+
+fn ui<B: Backend>(frame: &mut Frame<B>, race: &mut Race) {
+    let (area, status_area) = status_bar::split(frame.size());
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    for (player, half) in race.players.iter_mut().zip(halves) {
+        draw_game(frame, player, half);
+    }
+
+    if let Some(status_area) = status_area {
+        let player = &race.players[race.turn];
+        let bar = StatusBar::for_game(player).info(format!("Player {}", race.turn + 1));
+        status_bar::draw(frame, &player.theme, &bar, status_area);
+    }
+
+    if let Some(banner) = race.banner() {
+        let theme = &race.players[0].theme;
+        let color = match race.winner() {
+            Some(_) => theme.header_text_success_color,
+            None => theme.header_text_error_color,
+        };
+        let banner = Paragraph::new(banner)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+        frame.render_widget(banner, Rect::new(area.x, area.y, area.width, 1));
+    }
+}
@@ -0,0 +1,302 @@
+//! Maps key presses, modifiers included, to what they do in a game. The
+//! defaults can be changed with `key_bindings` in the settings file, e.g.
+//! `{"f1": "help", "ctrl+w": null}`.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Quits from anywhere, even with a menu open.
+    Quit,
+    /// Closes the open menu, or quits from the board.
+    Back,
+    Submit,
+    DeleteLetter,
+    ClearWord,
+    Help,
+    Hint,
+    Suspend,
+}
+
+impl Action {
+    const ALL: [Action; 8] = [
+        Action::Quit,
+        Action::Back,
+        Action::Submit,
+        Action::DeleteLetter,
+        Action::ClearWord,
+        Action::Help,
+        Action::Hint,
+        Action::Suspend,
+    ];
+
+    fn parse(name: &str) -> Result<Self, String> {
+        Action::ALL
+            .into_iter()
+            .find(|action| action.name() == name)
+            .ok_or_else(|| format!("unknown action '{name}'"))
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Back => "back",
+            Action::Submit => "submit",
+            Action::DeleteLetter => "delete_letter",
+            Action::ClearWord => "clear_word",
+            Action::Help => "help",
+            Action::Hint => "hint",
+            Action::Suspend => "suspend",
+        }
+    }
+}
+
+/// A key with its modifiers, written like `ctrl+c`, `esc` or `f1`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+const NAMED_KEYS: [(&str, KeyCode); 13] = [
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("backspace", KeyCode::Backspace),
+    ("tab", KeyCode::Tab),
+    ("space", KeyCode::Char(' ')),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("delete", KeyCode::Delete),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("insert", KeyCode::Insert),
+];
+
+impl Key {
+    const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Shift is part of the character itself, `?` arrives with it on some
+    /// terminals and without it on others.
+    fn from_event(event: KeyEvent) -> Self {
+        let modifiers = match event.code {
+            KeyCode::Char(_) => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        let code = match event.code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec;
+        loop {
+            let (modifier, tail) = match rest.split_once('+') {
+                Some((modifier, tail)) if !tail.is_empty() => (modifier, tail),
+                _ => break,
+            };
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{modifier}' in '{spec}'")),
+            };
+            rest = tail;
+        }
+
+        let lower = rest.to_ascii_lowercase();
+        let code = if let Some((_, code)) = NAMED_KEYS.iter().find(|(name, _)| *name == lower) {
+            *code
+        } else if let Some(number) = lower.strip_prefix('f').and_then(|n| n.parse().ok()) {
+            KeyCode::F(number)
+        } else {
+            let mut chars = rest.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return Err(format!("unknown key '{rest}' in '{spec}'")),
+            }
+        };
+
+        Ok(Key::from_event(KeyEvent::new(code, modifiers)))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl+"),
+            (KeyModifiers::ALT, "alt+"),
+            (KeyModifiers::SHIFT, "shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}")?;
+            }
+        }
+        match NAMED_KEYS.iter().find(|(_, code)| *code == self.code) {
+            Some((name, _)) => write!(f, "{name}"),
+            None => match self.code {
+                KeyCode::F(number) => write!(f, "f{number}"),
+                KeyCode::Char(c) => write!(f, "{c}"),
+                code => write!(f, "{code:?}"),
+            },
+        }
+    }
+}
+
+impl From<Key> for String {
+    fn from(key: Key) -> Self {
+        key.to_string()
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = String;
+
+    fn try_from(spec: String) -> Result<Self, String> {
+        Key::parse(&spec)
+    }
+}
+
+const DEFAULT_BINDINGS: [(Key, Action); 10] = [
+    (Key::new(KeyCode::Char('c'), KeyModifiers::CONTROL), Action::Quit),
+    (Key::new(KeyCode::Char('q'), KeyModifiers::CONTROL), Action::Quit),
+    (Key::new(KeyCode::Esc, KeyModifiers::NONE), Action::Back),
+    (Key::new(KeyCode::Enter, KeyModifiers::NONE), Action::Submit),
+    (Key::new(KeyCode::Backspace, KeyModifiers::NONE), Action::DeleteLetter),
+    (Key::new(KeyCode::Char('w'), KeyModifiers::CONTROL), Action::ClearWord),
+    (Key::new(KeyCode::Char('u'), KeyModifiers::CONTROL), Action::ClearWord),
+    (Key::new(KeyCode::Char('?'), KeyModifiers::NONE), Action::Help),
+    (Key::new(KeyCode::Tab, KeyModifiers::NONE), Action::Hint),
+    (Key::new(KeyCode::Char('z'), KeyModifiers::CONTROL), Action::Suspend),
+];
+
+/// Written as a list of key and action pairs, like `[["ctrl+c", "quit"]]`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KeyBindings {
+    bindings: Vec<(Key, Action)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            bindings: DEFAULT_BINDINGS.to_vec(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// The defaults with `overrides` from the settings applied, where `null`
+    /// unbinds a key. Entries that can't be read are skipped and returned as
+    /// errors, a typo shouldn't stop the game.
+    pub fn with_overrides(overrides: &BTreeMap<String, Option<String>>) -> (Self, Vec<String>) {
+        let mut key_bindings = Self::default();
+        let mut errors = Vec::new();
+        for (spec, action) in overrides {
+            let parsed = Key::parse(spec).and_then(|key| match action {
+                Some(name) => Action::parse(name)
+                    .map(|action| (key, Some(action)))
+                    .map_err(|err| format!("{err} for '{spec}'")),
+                None => Ok((key, None)),
+            });
+            let (key, action) = match parsed {
+                Ok(binding) => binding,
+                Err(err) => {
+                    errors.push(err);
+                    continue;
+                }
+            };
+            key_bindings.bindings.retain(|(other, _)| *other != key);
+            if let Some(action) = action {
+                key_bindings.bindings.push((key, action));
+            }
+        }
+        (key_bindings, errors)
+    }
+
+    /// The first key bound to `action`, for showing the player.
+    pub fn key_for(&self, action: Action) -> Option<Key> {
+        self.bindings
+            .iter()
+            .find(|(_, other)| *other == action)
+            .map(|(key, _)| *key)
+    }
+
+    /// What `event` does, if it is bound.
+    pub fn action(&self, event: KeyEvent) -> Option<Action> {
+        let key = Key::from_event(event);
+        self.bindings
+            .iter()
+            .find(|(other, _)| *other == key)
+            .map(|(_, action)| *action)
+    }
+}
+
+impl fmt::Display for KeyBindings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for action in Action::ALL {
+            let keys = self
+                .bindings
+                .iter()
+                .filter(|(_, other)| *other == action)
+                .map(|(key, _)| key.to_string())
+                .collect::<Vec<_>>();
+            let keys = match keys.is_empty() {
+                true => String::from("(unbound)"),
+                false => keys.join(", "),
+            };
+            writeln!(f, "{:<14} {keys}", action.name())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_read_back_as_written() {
+        let overrides = BTreeMap::from([
+            (String::from("f1"), Some(String::from("help"))),
+            (String::from("ctrl++"), Some(String::from("submit"))),
+            (String::from("enter"), None),
+        ]);
+        let (written, errors) = KeyBindings::with_overrides(&overrides);
+        assert!(errors.is_empty());
+
+        let json = serde_json::to_string(&written).unwrap();
+        let read: KeyBindings = serde_json::from_str(&json).unwrap();
+        let press = |code, modifiers| read.action(KeyEvent::new(code, modifiers));
+        assert_eq!(press(KeyCode::F(1), KeyModifiers::NONE), Some(Action::Help));
+        assert_eq!(press(KeyCode::Char('+'), KeyModifiers::CONTROL), Some(Action::Submit));
+        assert_eq!(press(KeyCode::Enter, KeyModifiers::NONE), None);
+        assert_eq!(press(KeyCode::Char('z'), KeyModifiers::CONTROL), Some(Action::Suspend));
+    }
+
+    #[test]
+    fn bad_overrides_are_skipped() {
+        let overrides = BTreeMap::from([
+            (String::from("f1"), Some(String::from("hlep"))),
+            (String::from("ctrl+w"), None),
+            (String::from("hyper+x"), Some(String::from("help"))),
+        ]);
+        let (key_bindings, errors) = KeyBindings::with_overrides(&overrides);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert_eq!(key_bindings.action(KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE)), None);
+        let ctrl_w = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(key_bindings.action(ctrl_w), None);
+    }
+}
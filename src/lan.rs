@@ -0,0 +1,594 @@
+//! Head-to-head races over the network.
+//!
+//! One instance hosts a [`serve`] loop on a TCP port and plays as a regular
+//! client over loopback; everyone else joins with [`Client::connect`]. Client
+//! and server talk newline-delimited JSON, one [`ClientMessage`] or
+//! [`ServerMessage`] per line. Progress updates only ever carry the colors of
+//! submitted rows, never the letters, so opponents' boards are spoiler-free.
+
+use crate::{
+    draw_game, handle_key, key_presses, poll_event,
+    status_bar::{self, StatusBar},
+    words, App, CharacterState, GameState, Mask,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame, Terminal,
+};
+
+pub const PROTOCOL_VERSION: u32 = 1;
+pub const DEFAULT_PORT: u16 = 4815;
+
+const OPPONENT_PANEL_WIDTH: u16 = 18;
+const MINI_BOARD_HEIGHT: u16 = 7;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerState {
+    InProgress,
+    Won,
+    Lost,
+}
+
+impl From<&GameState> for PlayerState {
+    fn from(state: &GameState) -> Self {
+        match state {
+            GameState::InProgress => PlayerState::InProgress,
+            GameState::Won => PlayerState::Won,
+            GameState::Lost(_) => PlayerState::Lost,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Must be the first message on a connection.
+    Join { version: u32, name: String },
+    /// The colors of every row submitted so far.
+    Progress {
+        rows: Vec<[CharacterState; 5]>,
+        state: PlayerState,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Reply to `join` with the puzzle everyone is racing on.
+    Welcome {
+        player_id: usize,
+        answer: String,
+        mask: String,
+    },
+    /// Another player joined or submitted a row.
+    Progress {
+        player_id: usize,
+        name: String,
+        rows: Vec<[CharacterState; 5]>,
+        state: PlayerState,
+    },
+    Left { player_id: usize },
+    Error { message: String },
+}
+
+pub struct HostOptions {
+    pub port: u16,
+    pub name: String,
+}
+
+pub struct JoinOptions {
+    pub addr: String,
+    pub name: String,
+}
+
+fn send<T: Serialize>(stream: &mut TcpStream, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    stream.write_all(&line)
+}
+
+/// A player's connection as the server writes to it. Every thread that
+/// sends to the player goes through the lock, so lines never interleave.
+type Writer = Arc<Mutex<TcpStream>>;
+
+fn reply(writer: &Writer, message: &ServerMessage) -> io::Result<()> {
+    send(&mut writer.lock().unwrap(), message)
+}
+
+/// Sends `message` to every writer. Write errors are ignored here; the
+/// failing peer's own connection thread cleans it up.
+fn broadcast(writers: Vec<Writer>, message: &ServerMessage) {
+    for writer in writers {
+        let _ = reply(&writer, message);
+    }
+}
+
+struct Peer {
+    id: usize,
+    name: String,
+    rows: Vec<[CharacterState; 5]>,
+    state: PlayerState,
+    writer: Writer,
+}
+
+impl Peer {
+    fn progress(&self) -> ServerMessage {
+        ServerMessage::Progress {
+            player_id: self.id,
+            name: self.name.clone(),
+            rows: self.rows.clone(),
+            state: self.state,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Lobby {
+    next_id: usize,
+    peers: Vec<Peer>,
+}
+
+impl Lobby {
+    /// Everyone except player `from`, to [`broadcast`] to once the lobby is
+    /// unlocked, so a slow player doesn't hold up the rest.
+    fn others(&self, from: usize) -> Vec<Writer> {
+        self.peers
+            .iter()
+            .filter(|peer| peer.id != from)
+            .map(|peer| Arc::clone(&peer.writer))
+            .collect()
+    }
+}
+
+/// Accepts players on `listener` forever, giving each of them `answer` and
+/// `mask` to race on.
+pub fn serve(listener: TcpListener, answer: &str, mask: Mask) {
+    let lobby = Arc::new(Mutex::new(Lobby::default()));
+
+    for stream in listener.incoming().flatten() {
+        let lobby = Arc::clone(&lobby);
+        let answer = answer.to_string();
+        let mask = mask.clone();
+        thread::spawn(move || {
+            let _ = handle_connection(stream, &lobby, &answer, &mask);
+        });
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    lobby: &Mutex<Lobby>,
+    answer: &str,
+    mask: &Mask,
+) -> io::Result<()> {
+    let mut player_id = None;
+    let result = read_messages(stream, lobby, answer, mask, &mut player_id);
+
+    if let Some(id) = player_id {
+        let others = {
+            let mut lobby = lobby.lock().unwrap();
+            lobby.peers.retain(|peer| peer.id != id);
+            lobby.others(id)
+        };
+        broadcast(others, &ServerMessage::Left { player_id: id });
+    }
+
+    result
+}
+
+fn read_messages(
+    stream: TcpStream,
+    lobby: &Mutex<Lobby>,
+    answer: &str,
+    mask: &Mask,
+    player_id: &mut Option<usize>,
+) -> io::Result<()> {
+    let writer: Writer = Arc::new(Mutex::new(stream.try_clone()?));
+
+    for line in BufReader::new(stream).lines() {
+        let message = match serde_json::from_str::<ClientMessage>(&line?) {
+            Ok(message) => message,
+            Err(err) => {
+                let message = format!("invalid message: {err}");
+                reply(&writer, &ServerMessage::Error { message })?;
+                continue;
+            }
+        };
+
+        match (message, *player_id) {
+            (ClientMessage::Join { version, .. }, _) if version != PROTOCOL_VERSION => {
+                let message =
+                    format!("protocol version {version} is not supported, expected {PROTOCOL_VERSION}");
+                reply(&writer, &ServerMessage::Error { message })?;
+                break;
+            }
+            (ClientMessage::Join { name, .. }, None) => {
+                // The new player hears about everyone before anyone else can
+                // write to it, so the welcome always comes first.
+                let (others, joined) = {
+                    let mut lobby = lobby.lock().unwrap();
+                    let id = lobby.next_id;
+                    lobby.next_id += 1;
+                    *player_id = Some(id);
+
+                    reply(
+                        &writer,
+                        &ServerMessage::Welcome {
+                            player_id: id,
+                            answer: answer.to_string(),
+                            mask: mask.to_string(),
+                        },
+                    )?;
+                    for peer in &lobby.peers {
+                        reply(&writer, &peer.progress())?;
+                    }
+
+                    let peer = Peer {
+                        id,
+                        name,
+                        rows: Vec::new(),
+                        state: PlayerState::InProgress,
+                        writer: Arc::clone(&writer),
+                    };
+                    let joined = peer.progress();
+                    lobby.peers.push(peer);
+                    (lobby.others(id), joined)
+                };
+                broadcast(others, &joined);
+            }
+            (ClientMessage::Join { .. }, Some(_)) => {
+                let message = String::from("already joined");
+                reply(&writer, &ServerMessage::Error { message })?;
+            }
+            (ClientMessage::Progress { rows, state }, Some(id)) => {
+                let mut lobby = lobby.lock().unwrap();
+                let Some(peer) = lobby.peers.iter_mut().find(|peer| peer.id == id) else {
+                    continue;
+                };
+                peer.rows = rows;
+                peer.state = state;
+                let progress = peer.progress();
+                let others = lobby.others(id);
+                drop(lobby);
+                broadcast(others, &progress);
+            }
+            (ClientMessage::Progress { .. }, None) => {
+                let message = String::from("send a join message first");
+                reply(&writer, &ServerMessage::Error { message })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A joined connection, with the puzzle the server handed out.
+pub struct Client {
+    stream: TcpStream,
+    inbox: Receiver<ServerMessage>,
+    name: String,
+    answer: String,
+    mask: Mask,
+    hosting_port: Option<u16>,
+}
+
+impl Client {
+    pub fn connect<A: ToSocketAddrs>(addr: A, name: &str) -> io::Result<Self> {
+        let mut stream = TcpStream::connect(addr)?;
+        send(
+            &mut stream,
+            &ClientMessage::Join {
+                version: PROTOCOL_VERSION,
+                name: name.to_string(),
+            },
+        )?;
+
+        let mut lines = BufReader::new(stream.try_clone()?).lines();
+        let welcome = lines.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::UnexpectedEof, "server closed the connection")
+        })??;
+        let (answer, mask) = match serde_json::from_str(&welcome)? {
+            ServerMessage::Welcome { answer, mask, .. } => (answer, mask),
+            ServerMessage::Error { message } => {
+                return Err(io::Error::other(message))
+            }
+            other => {
+                let message = format!("expected a welcome message, got {other:?}");
+                return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+            }
+        };
+        let mask =
+            Mask::parse(&mask).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let (sender, inbox) = mpsc::channel();
+        thread::spawn(move || {
+            for line in lines.map_while(Result::ok) {
+                if let Ok(message) = serde_json::from_str(&line) {
+                    if sender.send(message).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            stream,
+            inbox,
+            name: name.to_string(),
+            answer,
+            mask,
+            hosting_port: None,
+        })
+    }
+}
+
+/// Starts a server on `options.port` with a random answer and joins it.
+pub fn host(options: &HostOptions) -> io::Result<Client> {
+    let listener = TcpListener::bind(("0.0.0.0", options.port))?;
+    let port = listener.local_addr()?.port();
+    let answer = words::random_answer();
+    thread::spawn(move || serve(listener, answer, Mask::default()));
+
+    let mut client = Client::connect(("127.0.0.1", port), &options.name)?;
+    client.hosting_port = Some(port);
+    Ok(client)
+}
+
+struct Opponent {
+    name: String,
+    rows: Vec<[CharacterState; 5]>,
+    state: PlayerState,
+}
+
+struct LanGame {
+    app: App,
+    client: Client,
+    opponents: BTreeMap<usize, Opponent>,
+    connected: bool,
+}
+
+impl LanGame {
+    fn send_progress(&mut self) -> io::Result<()> {
+        let rows = self.app.guesses[..self.app.current_guess]
+            .iter()
+            .map(|row| row.char_states)
+            .collect();
+        let message = ClientMessage::Progress {
+            rows,
+            state: PlayerState::from(&self.app.state),
+        };
+        send(&mut self.client.stream, &message)
+    }
+
+    fn receive(&mut self) {
+        loop {
+            match self.client.inbox.try_recv() {
+                Ok(ServerMessage::Progress {
+                    player_id,
+                    name,
+                    rows,
+                    state,
+                }) => {
+                    self.opponents
+                        .insert(player_id, Opponent { name, rows, state });
+                }
+                Ok(ServerMessage::Left { player_id }) => {
+                    self.opponents.remove(&player_id);
+                }
+                Ok(ServerMessage::Welcome { .. } | ServerMessage::Error { .. }) => {}
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.connected = false;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, client: Client) -> io::Result<()> {
+    let mut app = App::new(&client.answer, client.mask.clone());
+    app.title = client.name.clone();
+    app.mode = "LAN";
+    let mut game = LanGame {
+        app,
+        client,
+        opponents: BTreeMap::new(),
+        connected: true,
+    };
+
+    loop {
+        game.receive();
+        terminal.draw(|f| ui(f, &mut game))?;
+
+        let timeout = Duration::from_millis(100);
+        let Some(event) = poll_event(terminal, &game.app.key_bindings, timeout)? else {
+            continue;
+        };
+        for key in key_presses(&game.app, event) {
+            let guesses_before = game.app.current_guess;
+            if handle_key(&mut game.app, key).is_break() {
+                return Ok(());
+            }
+            // A lost connection leaves the board to be finished alone.
+            if game.app.current_guess != guesses_before && game.connected {
+                game.connected = game.send_progress().is_ok();
+            }
+        }
+    }
+}
+
+fn ui<B: Backend>(frame: &mut Frame<B>, game: &mut LanGame) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Min(0),
+            Constraint::Length(OPPONENT_PANEL_WIDTH),
+        ])
+        .split(frame.size());
+
+    let (board_area, status_area) = status_bar::split(columns[0]);
+    draw_game(frame, &mut game.app, board_area);
+    if let Some(status_area) = status_area {
+        let mut bar = StatusBar::for_game(&game.app);
+        if !game.connected {
+            bar = bar.info("disconnected");
+        }
+        status_bar::draw(frame, &game.app.theme, &bar, status_area);
+    }
+    draw_opponents(frame, game, columns[1]);
+}
+
+fn draw_opponents<B: Backend>(frame: &mut Frame<B>, game: &LanGame, chunk: Rect) {
+    let title = match (game.connected, game.client.hosting_port) {
+        (false, _) => String::from("Disconnected"),
+        (true, Some(port)) => format!("Hosting :{port}"),
+        (true, None) => String::from("Opponents"),
+    };
+    let panel = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().fg(game.app.theme.border_color))
+        .title(title)
+        .border_type(BorderType::Plain);
+    let inner = panel.inner(chunk);
+    frame.render_widget(panel, chunk);
+
+    if game.opponents.is_empty() {
+        let waiting = Paragraph::new("Waiting for players...")
+            .wrap(tui::widgets::Wrap { trim: true })
+            .style(Style::default().add_modifier(Modifier::DIM));
+        frame.render_widget(waiting, inner);
+        return;
+    }
+
+    let fits = (inner.height / MINI_BOARD_HEIGHT) as usize;
+    for (idx, opponent) in game.opponents.values().take(fits).enumerate() {
+        let chunk = Rect::new(
+            inner.x,
+            inner.y + idx as u16 * MINI_BOARD_HEIGHT,
+            inner.width,
+            MINI_BOARD_HEIGHT,
+        );
+        frame.render_widget(mini_board(&game.app, opponent), chunk);
+    }
+}
+
+/// Renders an opponent's board as colored blocks only, without letters.
+fn mini_board(app: &App, opponent: &Opponent) -> Paragraph<'static> {
+    let lines = (0..app.guesses.len())
+        .map(|row_idx| {
+            let cells = (0..5).map(|char_idx| match opponent.rows.get(row_idx) {
+                Some(row) => mini_cell(app, row[char_idx]),
+                None => Span::styled("··", Style::default().add_modifier(Modifier::DIM)),
+            });
+            let mut spans = Vec::new();
+            for (idx, cell) in cells.enumerate() {
+                if idx > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(cell);
+            }
+            Spans::from(spans)
+        })
+        .collect::<Vec<_>>();
+
+    let title = match opponent.state {
+        PlayerState::InProgress => opponent.name.clone(),
+        PlayerState::Won => format!("{} ✓", opponent.name),
+        PlayerState::Lost => format!("{} ✗", opponent.name),
+    };
+
+    Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(title),
+    )
+}
+
+fn mini_cell(app: &App, state: CharacterState) -> Span<'static> {
+    let color = app.theme.guess_color(state);
+    if let Some(marker) = app.theme.marker(state) {
+        return Span::styled(format!("{marker}{marker}"), Style::default().fg(color));
+    }
+    let text = match state {
+        CharacterState::Unknown | CharacterState::Masked | CharacterState::Delayed => "░░",
+        _ => "██",
+    };
+    Span::styled(text, Style::default().fg(color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Shutdown;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn players_see_each_others_progress() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, "world", Mask::default()));
+
+        let mut host = Client::connect(addr, "host").unwrap();
+        let guest = Client::connect(addr, "guest").unwrap();
+        assert_eq!(guest.answer, "world");
+        assert_eq!(guest.mask.to_string(), host.mask.to_string());
+
+        let joined = host.inbox.recv_timeout(TIMEOUT).unwrap();
+        assert_eq!(
+            joined,
+            ServerMessage::Progress {
+                player_id: 1,
+                name: String::from("guest"),
+                rows: Vec::new(),
+                state: PlayerState::InProgress,
+            }
+        );
+
+        let rows = vec![[CharacterState::Correct; 5]];
+        let progress = ClientMessage::Progress {
+            rows: rows.clone(),
+            state: PlayerState::Won,
+        };
+        send(&mut host.stream, &progress).unwrap();
+        // The guest first hears of the host as it was when the guest joined.
+        let updates = [
+            guest.inbox.recv_timeout(TIMEOUT).unwrap(),
+            guest.inbox.recv_timeout(TIMEOUT).unwrap(),
+        ];
+        assert_eq!(
+            updates[1],
+            ServerMessage::Progress {
+                player_id: 0,
+                name: String::from("host"),
+                rows,
+                state: PlayerState::Won,
+            }
+        );
+
+        // The client's reader thread holds on to the socket, dropping the
+        // client alone would leave it open.
+        host.stream.shutdown(Shutdown::Both).unwrap();
+        let left = guest.inbox.recv_timeout(TIMEOUT).unwrap();
+        assert_eq!(left, ServerMessage::Left { player_id: 0 });
+    }
+}
@@ -1,9 +1,68 @@
+mod adversary;
+mod bench;
+mod bot;
+mod cli;
+mod colors;
+mod endless;
+mod font;
+mod help;
+mod history;
+mod hints;
+mod hotseat;
+mod keys;
+mod lan;
+mod mask_editor;
+mod multi;
+mod plain;
+mod puzzle_code;
+mod replay;
+mod saved_game;
+mod settings;
+mod share;
+mod signals;
+mod solver;
+mod stats;
+mod status_bar;
+mod storage;
+mod timer;
+mod toast;
+mod tutorial;
+mod words;
+
+use adversary::Adversary;
+use bot::BotOptions;
+use cli::Command;
+use colors::ColorDepth;
+use hints::{Hint, HintMenu};
+use keys::{Action, KeyBindings};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    cursor::Show,
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste,
+        EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use std::{error::Error, io, str::Chars};
+use rand::Rng;
+use replay::{Recorder, Replay};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use stats::Stats;
+use std::{
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Stdout, Write},
+    panic,
+    ops::ControlFlow,
+    path::PathBuf,
+    str::Chars,
+    time::Duration,
+};
+use status_bar::StatusBar;
+use timer::Timer;
+use toast::{Severity, Toasts};
 use tui::{
     backend::{Backend, CrosstermBackend},
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -18,16 +77,22 @@ struct Theme {
     border_color: Color,
     header_text_error_color: Color,
     header_text_success_color: Color,
+    header_text_warning_color: Color,
     empty_row_block_color: Color,
     guess_in_right_place_color: Color,
     guess_in_word_color: Color,
     guess_not_in_word_color: Color,
+    guess_letter_present_color: Color,
+    hint_color: Color,
     keyboard_not_guessed_color: Color,
     keyboard_in_right_place_color: Color,
     keyboard_in_word_color: Color,
     keyboard_not_in_word_color: Color,
     row_border_thickness: BorderType,
     guessed_row_border_thickness: BorderType,
+    /// Marks every state with a glyph too, for players and terminals that
+    /// can't tell the colors apart.
+    markers: bool,
 }
 
 impl Default for Theme {
@@ -36,23 +101,164 @@ impl Default for Theme {
     }
 }
 
+/// The themes that can be picked with `theme` in the settings.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    /// The green, yellow and grey of the original Wordle.
+    Classic,
+    /// Wordle's orange and blue, for colorblind players.
+    HighContrast,
+}
+
 impl Theme {
     pub fn light_theme() -> Self {
         Self {
             border_color: Color::Black,
             active_row_input_color: Color::Black,
             header_text_success_color: Color::Green,
+            header_text_warning_color: Color::Yellow,
             header_text_error_color: Color::Red,
             empty_row_block_color: Color::Gray,
             guess_in_right_place_color: Color::Green,
             guess_in_word_color: Color::Yellow,
             guess_not_in_word_color: Color::DarkGray,
+            guess_letter_present_color: Color::Cyan,
+            hint_color: Color::Magenta,
             keyboard_not_guessed_color: Color::Black,
             keyboard_in_right_place_color: Color::Green,
             keyboard_in_word_color: Color::Yellow,
             keyboard_not_in_word_color: Color::Gray,
             row_border_thickness: BorderType::Plain,
             guessed_row_border_thickness: BorderType::Thick,
+            markers: false,
+        }
+    }
+
+    /// No colors at all, so every state is told apart by its marker.
+    pub fn monochrome_theme() -> Self {
+        Self {
+            border_color: Color::Reset,
+            active_row_input_color: Color::Reset,
+            header_text_success_color: Color::Reset,
+            header_text_warning_color: Color::Reset,
+            header_text_error_color: Color::Reset,
+            empty_row_block_color: Color::Reset,
+            guess_in_right_place_color: Color::Reset,
+            guess_in_word_color: Color::Reset,
+            guess_not_in_word_color: Color::Reset,
+            guess_letter_present_color: Color::Reset,
+            hint_color: Color::Reset,
+            keyboard_not_guessed_color: Color::Reset,
+            keyboard_in_right_place_color: Color::Reset,
+            keyboard_in_word_color: Color::Reset,
+            keyboard_not_in_word_color: Color::Reset,
+            markers: true,
+            ..Theme::light_theme()
+        }
+    }
+
+    pub fn classic_theme() -> Self {
+        let text = Color::Rgb(0xd7, 0xda, 0xdc);
+        let green = Color::Rgb(0x6a, 0xaa, 0x64);
+        let yellow = Color::Rgb(0xc9, 0xb4, 0x58);
+        Self {
+            border_color: text,
+            active_row_input_color: text,
+            header_text_success_color: green,
+            header_text_warning_color: yellow,
+            header_text_error_color: Color::Rgb(0xe0, 0x60, 0x58),
+            empty_row_block_color: Color::Rgb(0x3a, 0x3a, 0x3c),
+            guess_in_right_place_color: green,
+            guess_in_word_color: yellow,
+            guess_not_in_word_color: Color::Rgb(0x78, 0x7c, 0x7e),
+            guess_letter_present_color: Color::Rgb(0x5f, 0xa8, 0xb0),
+            hint_color: Color::Rgb(0xb4, 0x8e, 0xad),
+            keyboard_not_guessed_color: text,
+            keyboard_in_right_place_color: green,
+            keyboard_in_word_color: yellow,
+            keyboard_not_in_word_color: Color::Rgb(0x56, 0x57, 0x58),
+            ..Theme::light_theme()
+        }
+    }
+
+    pub fn high_contrast_theme() -> Self {
+        let orange = Color::Rgb(0xf5, 0x79, 0x3a);
+        let blue = Color::Rgb(0x85, 0xc0, 0xf9);
+        Self {
+            header_text_success_color: orange,
+            header_text_warning_color: blue,
+            guess_in_right_place_color: orange,
+            guess_in_word_color: blue,
+            keyboard_in_right_place_color: orange,
+            keyboard_in_word_color: blue,
+            ..Theme::classic_theme()
+        }
+    }
+
+    /// The monochrome theme if `NO_COLOR` is set, otherwise the theme from
+    /// the settings in the colors the terminal has, with markers if the
+    /// settings ask for them.
+    pub fn detect(settings: &Settings) -> Self {
+        if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return Theme::monochrome_theme();
+        }
+        let theme = match settings.theme {
+            ThemeName::Dark => Theme::dark_theme(),
+            ThemeName::Light => Theme::light_theme(),
+            ThemeName::Classic => Theme::classic_theme(),
+            ThemeName::HighContrast => Theme::high_contrast_theme(),
+        };
+        let depth = settings.color_depth.unwrap_or_else(ColorDepth::detect);
+        Theme {
+            markers: settings.markers,
+            ..theme.with_depth(depth)
+        }
+    }
+
+    /// Every color brought down to what a terminal with `depth` can show.
+    fn with_depth(self, depth: ColorDepth) -> Self {
+        let convert = |color| depth.convert(color);
+        Self {
+            active_row_input_color: convert(self.active_row_input_color),
+            border_color: convert(self.border_color),
+            header_text_error_color: convert(self.header_text_error_color),
+            header_text_success_color: convert(self.header_text_success_color),
+            header_text_warning_color: convert(self.header_text_warning_color),
+            empty_row_block_color: convert(self.empty_row_block_color),
+            guess_in_right_place_color: convert(self.guess_in_right_place_color),
+            guess_in_word_color: convert(self.guess_in_word_color),
+            guess_not_in_word_color: convert(self.guess_not_in_word_color),
+            guess_letter_present_color: convert(self.guess_letter_present_color),
+            hint_color: convert(self.hint_color),
+            keyboard_not_guessed_color: convert(self.keyboard_not_guessed_color),
+            keyboard_in_right_place_color: convert(self.keyboard_in_right_place_color),
+            keyboard_in_word_color: convert(self.keyboard_in_word_color),
+            keyboard_not_in_word_color: convert(self.keyboard_not_in_word_color),
+            ..self
+        }
+    }
+
+    /// The marker shown with `state` when markers are on.
+    fn marker(&self, state: CharacterState) -> Option<char> {
+        self.markers.then(|| state.marker()).filter(|marker| *marker != ' ')
+    }
+
+    /// Color of a submitted cell in the given state.
+    pub fn guess_color(&self, state: CharacterState) -> Color {
+        match state {
+            CharacterState::Correct => self.guess_in_right_place_color,
+            CharacterState::WrongPlace => self.guess_in_word_color,
+            CharacterState::NotInWord => self.guess_not_in_word_color,
+            CharacterState::Unknown | CharacterState::Delayed => self.keyboard_not_guessed_color,
+            CharacterState::Masked => self.active_row_input_color,
+            CharacterState::InWord => self.guess_letter_present_color,
+            CharacterState::LetterHidden(Shade::Correct) => self.guess_in_right_place_color,
+            CharacterState::LetterHidden(Shade::WrongPlace) => self.guess_in_word_color,
+            CharacterState::LetterHidden(Shade::NotInWord) => self.guess_not_in_word_color,
         }
     }
 
@@ -72,15 +278,62 @@ pub struct BlockTheme {
     pub border_color: Color,
     pub border_thickness: BorderType,
     pub text_color: Color,
+    /// Drawn in the top left corner of the border.
+    pub corner: Option<char>,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 enum CharacterState {
     WrongPlace,
     Correct,
     NotInWord,
     Unknown,
-    Masked
+    Masked,
+    /// A binary cell's letter is somewhere in the word, position unknown.
+    InWord,
+    /// A delayed cell whose feedback shows up once the next row is submitted.
+    Delayed,
+    /// Feedback of a cell whose letter is hidden once it is submitted.
+    LetterHidden(Shade),
+}
+
+/// The feedback a letter-hidden cell still gives away.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Shade {
+    WrongPlace,
+    Correct,
+    NotInWord,
+}
+
+impl CharacterState {
+    /// Whether the state says anything about its letter, i.e. whether it
+    /// belongs on the keyboard.
+    fn is_feedback(self) -> bool {
+        matches!(
+            self,
+            CharacterState::WrongPlace
+                | CharacterState::Correct
+                | CharacterState::NotInWord
+                | CharacterState::InWord
+        )
+    }
+
+    /// A glyph standing in for the state's color: `=` right place, `+` in
+    /// the word, `-` not in it, `#` masked and `~` not known yet.
+    fn marker(self) -> char {
+        match self {
+            CharacterState::Correct | CharacterState::LetterHidden(Shade::Correct) => '=',
+            CharacterState::WrongPlace
+            | CharacterState::InWord
+            | CharacterState::LetterHidden(Shade::WrongPlace) => '+',
+            CharacterState::NotInWord | CharacterState::LetterHidden(Shade::NotInWord) => '-',
+            CharacterState::Masked => '#',
+            CharacterState::Delayed => '~',
+            CharacterState::Unknown => ' ',
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -97,51 +350,35 @@ enum GameState {
     Lost(String),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 struct Row {
     guess: String,
     char_states: [CharacterState; 5],
+    /// Set when the row was spent on a hint instead of a guess.
+    hint: Option<Hint>,
 }
 
 impl Row {
     fn from_current(app: &mut App) -> Row {
-        let mut char_states = [CharacterState::Unknown; 5];
-        for (char_idx, (correct_char, char)) in app
-            .correct_word
-            .clone()
-            .chars()
-            .zip(app.input.clone().chars())
-            .enumerate()
-        {
-                            if !app.mask.get_mask(app.current_guess, char_idx)  {
-            if correct_char == char {
-                char_states[char_idx] = CharacterState::Correct;
+        let char_states = match app.adversary.as_mut() {
+            Some(adversary) => {
+                let char_states = adversary.respond(&app.input, &app.mask, app.current_guess);
+                app.correct_word = adversary.answer().to_string();
+                char_states
             }
-                }
-        }
-
-        for (char_idx, char) in app.input.clone().chars().enumerate() {
-                            if !app.mask.get_mask(app.current_guess, char_idx)  {
-            if char_states[char_idx] == CharacterState::Unknown {
-                if app.correct_word.contains(char) {
-                    char_states[char_idx] = CharacterState::WrongPlace;
-                } else {
-                    char_states[char_idx] = CharacterState::NotInWord;
-                }
-            }
-                }
-        }
+            None => score_guess(&app.correct_word, &app.input, &app.mask, app.current_guess),
+        };
         let guess = app.input.drain(..).collect::<String>();
         Row {
             guess,
-            char_states: char_states.try_into().unwrap(), // Shouldn't fail since input is always 5 characters long
-                                                          // state: RowState::AlreadyGuessed,
+            char_states,
+            hint: None,
         }
     }
 
-    fn new(mask: Mask, row_idx: usize) -> Self {
+    fn new(mask: &Mask, row_idx: usize) -> Self {
         let char_states = (0..5).map(|x| {
-            let masked = mask.get_mask(row_idx, x);
+            let masked = mask.get_mask(row_idx, x) == CellKind::Hidden;
             if masked {
                 CharacterState::Masked
             } else {
@@ -152,6 +389,7 @@ impl Row {
         Self {
             guess: " ".to_string(),
             char_states,
+            hint: None,
         }
     }
 
@@ -165,35 +403,214 @@ impl Default for Row {
         Row {
             guess: "".to_string(),
             char_states: [CharacterState::Unknown; 5],
+            hint: None,
         }
     }
 }
 
+/// How much feedback a cell of the board gives.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CellKind {
+    Visible,
+    /// No feedback at all.
+    Hidden,
+    /// Only whether the letter is in the word, not whether it is in place.
+    Binary,
+    /// Full feedback, but only after the next row is submitted.
+    Delayed,
+    /// Full feedback, but the letter itself is hidden once submitted.
+    LetterHidden,
+}
+
+impl CellKind {
+    const ALL: [CellKind; 5] = [
+        CellKind::Visible,
+        CellKind::Hidden,
+        CellKind::Binary,
+        CellKind::Delayed,
+        CellKind::LetterHidden,
+    ];
+
+    /// The character used for the cell in mask patterns.
+    fn symbol(self) -> char {
+        match self {
+            CellKind::Visible => '.',
+            CellKind::Hidden => '#',
+            CellKind::Binary => '?',
+            CellKind::Delayed => '~',
+            CellKind::LetterHidden => '*',
+        }
+    }
+
+    fn from_symbol(symbol: char) -> Option<Self> {
+        CellKind::ALL.into_iter().find(|kind| kind.symbol() == symbol)
+    }
+
+    /// Shown in cells of this kind before a letter is typed into them, so
+    /// the mask can be read off the board.
+    fn placeholder(self) -> char {
+        match self {
+            CellKind::Visible | CellKind::Hidden => ' ',
+            kind => kind.symbol(),
+        }
+    }
+
+    /// Border of cells of this kind, `default` unless the kind has its own.
+    fn border(self, default: BorderType) -> BorderType {
+        match self {
+            CellKind::Binary => BorderType::Rounded,
+            CellKind::Delayed => BorderType::Double,
+            _ => default,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 struct Mask {
-    items: [bool; 25],
+    /// One kind per cell, row by row, five cells to a row.
+    items: Vec<CellKind>,
 }
 
 impl Mask {
-    fn get_mask(&self, row_idx: usize, char_idx: usize) -> bool {
-        self.items[(row_idx * 5) + char_idx]
-    }    
+    /// The kind of a cell. Rows past the end of the mask are fully visible.
+    fn get_mask(&self, row_idx: usize, char_idx: usize) -> CellKind {
+        self.items
+            .get((row_idx * 5) + char_idx)
+            .copied()
+            .unwrap_or(CellKind::Visible)
+    }
+
+    /// Builds a mask of `rows` rows where every cell is hidden with
+    /// probability `density`.
+    fn random<R: Rng>(rng: &mut R, density: f64, rows: usize) -> Self {
+        Self {
+            items: (0..rows * 5)
+                .map(|_| if rng.gen_bool(density) { CellKind::Hidden } else { CellKind::Visible })
+                .collect(),
+        }
+    }
+
+    /// Parses a mask written row by row, using `.` for visible cells, `#` for
+    /// hidden ones, `?` for binary, `~` for delayed and `*` for letter hidden
+    /// cells, e.g. `..#../.?.../.#~../..#../..*..`. Whitespace and `/`
+    /// between rows are ignored.
+    fn parse(spec: &str) -> Result<Self, String> {
+        let items = spec
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '/')
+            .map(|c| {
+                CellKind::from_symbol(c).ok_or(format!(
+                    "invalid mask cell '{c}', expected one of '.', '#', '?', '~' or '*'"
+                ))
+            })
+            .collect::<Result<Vec<CellKind>, String>>()?;
+
+        if items.is_empty() || items.len() % 5 != 0 {
+            return Err(format!(
+                "mask needs five cells per row, got {} cells",
+                items.len()
+            ));
+        }
+        Ok(Self { items })
+    }
+
+    /// Where the mask saved as `name` lives, inside the data directory.
+    fn path(name: &str) -> Result<PathBuf, String> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "'{name}' is not a valid mask name, use letters, digits, '-' and '_'"
+            ));
+        }
+        let dir = storage::data_dir().ok_or("could not find a data directory")?;
+        Ok(dir.join("masks").join(format!("{name}.mask")))
+    }
+
+    /// Loads a mask saved by the mask editor.
+    fn load(name: &str) -> Result<Self, String> {
+        let path = Mask::path(name)?;
+        let spec = fs::read_to_string(&path)
+            .map_err(|err| format!("can't read mask '{name}' from {}: {err}", path.display()))?;
+        Mask::parse(&spec).map_err(|err| format!("mask file {} is invalid: {err}", path.display()))
+    }
+
+    /// Saves the mask under `name`, one row per line, and returns the path.
+    fn save(&self, name: &str) -> Result<PathBuf, String> {
+        let path = Mask::path(name)?;
+        let rows = self.to_string().replace('/', "\n");
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, rows + "\n"))
+            .map_err(|err| format!("can't save mask to {}: {err}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Reads a mask argument: `default`, a cell pattern, or the name of a
+    /// saved mask.
+    fn from_arg(arg: &str) -> Result<Self, String> {
+        match arg {
+            "default" => Ok(Mask::default()),
+            _ if arg.chars().all(|c| c == '/' || CellKind::from_symbol(c).is_some()) => {
+                Mask::parse(arg)
+            }
+            name => Mask::load(name),
+        }
+    }
+}
+
+impl fmt::Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self
+            .items
+            .chunks(5)
+            .map(|row| row.iter().map(|kind| kind.symbol()).collect())
+            .collect::<Vec<String>>();
+        write!(f, "{}", rows.join("/"))
+    }
 }
 
 impl Default for Mask {
-    #[rustfmt::skip]
     fn default() -> Self {
-        Self {
-            items: [
-             false, false, true, false, false,   
-             false, true, false, false, false,   
-             false, true, false, false, false,   
-             false, false, true, false, false,   
-             false, false, true, false, false,   
-            ]
-        }
+        Mask::parse("..#../.#.../.#.../..#../..#..").expect("the default mask is valid")
     }
 }
 
+/// Scores `guess` against `answer` on the given row, giving each cell only as
+/// much feedback as its kind in the mask allows.
+fn score_guess(answer: &str, guess: &str, mask: &Mask, row_idx: usize) -> [CharacterState; 5] {
+    let mut char_states = [CharacterState::Unknown; 5];
+    for (char_idx, char_state) in char_states.iter_mut().enumerate() {
+        let full = score_cell(answer, guess, char_idx);
+        *char_state = match (mask.get_mask(row_idx, char_idx), full) {
+            (CellKind::Visible, _) => full,
+            (CellKind::Hidden, _) => CharacterState::Masked,
+            (CellKind::Binary, CharacterState::NotInWord) => CharacterState::NotInWord,
+            (CellKind::Binary, _) => CharacterState::InWord,
+            (CellKind::Delayed, _) => CharacterState::Delayed,
+            (CellKind::LetterHidden, CharacterState::Correct) => {
+                CharacterState::LetterHidden(Shade::Correct)
+            }
+            (CellKind::LetterHidden, CharacterState::WrongPlace) => {
+                CharacterState::LetterHidden(Shade::WrongPlace)
+            }
+            (CellKind::LetterHidden, _) => CharacterState::LetterHidden(Shade::NotInWord),
+        };
+    }
+    char_states
+}
+
+/// The full feedback for one cell of `guess`, ignoring the mask.
+fn score_cell(answer: &str, guess: &str, char_idx: usize) -> CharacterState {
+    match (answer.chars().nth(char_idx), guess.chars().nth(char_idx)) {
+        (Some(correct_char), Some(char)) if correct_char == char => CharacterState::Correct,
+        (_, Some(char)) if answer.contains(char) => CharacterState::WrongPlace,
+        _ => CharacterState::NotInWord,
+    }
+}
 
 /// App holds the state of the application
 struct App {
@@ -205,6 +622,21 @@ struct App {
     theme: Theme,
     state: GameState,
     mask: Mask,
+    title: String,
+    /// The name of the way this game is played, for the status bar.
+    mode: &'static str,
+    timer: Option<Timer>,
+    /// Picks the feedback instead of `correct_word` in adversarial games.
+    adversary: Option<Adversary>,
+    /// Once the game is over, whether masked cells show the feedback they
+    /// hid instead of the board as played.
+    reveal_masked: bool,
+    hint_menu: Option<HintMenu>,
+    show_help: bool,
+    /// Fixed by the settings, otherwise fit to the terminal on every draw.
+    cell_size: Option<CellSize>,
+    key_bindings: KeyBindings,
+    toasts: Toasts,
 }
 
 impl App {
@@ -271,49 +703,320 @@ impl App {
             _ => (),
         }
     }
+
+    fn update_key_status(&mut self, row: &Row) {
+        for (char, state) in row.chars().zip(row.char_states) {
+            if state == CharacterState::Correct {
+                self.set_letter_state(char, CharacterState::Correct);
+            }
+        }
+
+        for (char, state) in row.chars().zip(row.char_states) {
+            if state.is_feedback() && self.get_letter_state(char) == CharacterState::Unknown {
+                // The keyboard only tells letters in the word from the rest.
+                let state = match state {
+                    CharacterState::InWord => CharacterState::WrongPlace,
+                    state => state,
+                };
+                self.set_letter_state(char, state);
+            }
+        }
+    }
 }
 
 impl Default for App {
     fn default() -> App {
+        App::new("world", Mask::default())
+    }
+}
+
+impl App {
+    fn new(correct_word: &str, mask: Mask) -> App {
+        App::with_guesses(correct_word, mask, MAX_GUESSES)
+    }
+
+    fn with_guesses(correct_word: &str, mask: Mask, guesses: usize) -> App {
+        let settings = Settings::load();
         App {
             input: String::new(),
-            guesses: vec![
-                Row::new(Mask::default(), 0 ),
-                Row::new(Mask::default(), 1 ),
-                Row::new(Mask::default(), 2 ),
-                Row::new(Mask::default(), 3 ),
-                Row::new(Mask::default(), 4 ),
-            ],
+            guesses: (0..guesses).map(|row_idx| Row::new(&mask, row_idx)).collect(),
             current_guess: 0,
-            correct_word: "world".to_string().to_ascii_lowercase(),
+            correct_word: correct_word.to_ascii_lowercase(),
             key_status: [CharacterState::Unknown; 26],
-            theme: Theme::dark_theme(),
+            theme: Theme::detect(&settings),
             state: GameState::InProgress,
-            mask: Mask::default(),
+            mask,
+            title: String::from("Spotle Tui"),
+            mode: "Classic",
+            timer: None,
+            adversary: None,
+            reveal_masked: false,
+            hint_menu: None,
+            show_help: false,
+            cell_size: settings.cell_size,
+            key_bindings: KeyBindings::with_overrides(&settings.key_bindings).0,
+            toasts: Toasts::default(),
+        }
+    }
+
+    /// A game without a fixed answer, where an [`Adversary`] dodges every
+    /// guess for as long as the word list allows.
+    fn adversarial(mask: Mask) -> App {
+        let adversary = Adversary::default();
+        let answer = adversary.answer();
+        App {
+            title: String::from("Spotle Tui - Adversarial"),
+            mode: "Adversarial",
+            adversary: Some(adversary),
+            ..App::new(answer, mask)
+        }
+    }
+
+    /// Fills in the feedback of the delayed cells on row `row_idx`, once the
+    /// row after it is submitted or the game is over.
+    fn reveal_delayed(&mut self, row_idx: usize) {
+        let Some(row) = self.guesses.get(row_idx) else {
+            return;
+        };
+        let delayed = (0..5)
+            .filter(|idx| row.char_states[*idx] == CharacterState::Delayed)
+            .collect::<Vec<_>>();
+        if delayed.is_empty() {
+            return;
+        }
+
+        let guess = row.guess.clone();
+        if let Some(adversary) = self.adversary.as_mut() {
+            adversary.settle(&guess, &delayed);
+            self.correct_word = adversary.answer().to_string();
+        }
+        for idx in delayed {
+            self.guesses[row_idx].char_states[idx] = score_cell(&self.correct_word, &guess, idx);
+        }
+        let row = self.guesses[row_idx].clone();
+        self.update_key_status(&row);
+    }
+
+    /// The hints bought so far, in the order they were bought.
+    fn hints(&self) -> impl Iterator<Item = Hint> + '_ {
+        self.guesses.iter().rev().map_while(|row| row.hint)
+    }
+
+    /// Rows left for guessing once the ones spent on hints are taken off.
+    fn playable_rows(&self) -> usize {
+        self.guesses.len() - self.hints().count()
+    }
+
+    /// Whether any submitted cell was masked, i.e. whether there is anything
+    /// to reveal once the game is over.
+    fn has_masked_feedback(&self) -> bool {
+        self.guesses[..self.current_guess]
+            .iter()
+            .any(|row| row.char_states.contains(&CharacterState::Masked))
+    }
+
+    /// The feedback a masked cell hid, while the revealed board is shown.
+    fn revealed_state(&self, row_idx: usize, char_idx: usize) -> Option<CharacterState> {
+        let row = &self.guesses[row_idx];
+        let shown = self.reveal_masked
+            && self.state != GameState::InProgress
+            && row_idx < self.current_guess
+            && row.char_states[char_idx] == CharacterState::Masked;
+        shown.then(|| score_cell(&self.correct_word, &row.guess, char_idx))
+    }
+
+    /// Ends the game as a loss once the timer's limit has run out.
+    fn tick(&mut self) {
+        if self.state == GameState::InProgress && self.timer.as_ref().is_some_and(Timer::expired) {
+            self.state = GameState::Lost(self.correct_word.clone());
+            if let Some(timer) = self.timer.as_mut() {
+                timer.stop();
+            }
         }
     }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
+    match cli::parse(std::env::args().skip(1)) {
+        Ok(Command::Play(options)) if options.bot => {
+            let bot_options = BotOptions {
+                answer: options.puzzle.as_ref().map(|puzzle| puzzle.answer.clone()),
+                mask: options
+                    .mask
+                    .or(options.puzzle.map(|puzzle| puzzle.mask))
+                    .unwrap_or_default(),
+                adversarial: options.adversarial,
+            };
+            bot::run(&bot_options, io::stdin().lock(), io::stdout())?;
+            Ok(())
+        }
+        Ok(Command::Play(options)) => {
+            // Only a plain game picks up a saved one, any option asks for
+            // something new.
+            let resumable = options.puzzle.is_none()
+                && options.mask.is_none()
+                && !options.adversarial
+                && options.record.is_none()
+                && options.timer.is_none()
+                && !options.plain;
+            let (mut app, saved) = match resumable.then(saved_game::resume).flatten() {
+                Some((mut app, saved)) => {
+                    app.toasts.push(Severity::Info, "Picked up your unfinished game");
+                    (app, Some(saved))
+                }
+                None => {
+                    let app = match options.puzzle {
+                        Some(puzzle) => App {
+                            mode: "Shared puzzle",
+                            ..App::new(&puzzle.answer, puzzle.mask)
+                        },
+                        None => App::default(),
+                    };
+                    let app = match options.mask {
+                        Some(mask) => App {
+                            mode: "Custom mask",
+                            ..App::new(&app.correct_word, mask)
+                        },
+                        None => app,
+                    };
+                    let app = if options.adversarial {
+                        App::adversarial(app.mask)
+                    } else {
+                        app
+                    };
+                    let app = App {
+                        timer: options.timer.map(Timer::new),
+                        ..app
+                    };
+                    (app, None)
+                }
+            };
+            app.theme.markers |= options.markers;
+            let recorder = match options.record {
+                Some(path) => Some(Recorder::create(&path, &app)?),
+                None => None,
+            };
+            if options.plain {
+                let stdin = io::stdin();
+                if let Some(app) = plain::run(app, recorder, stdin.lock(), io::stdout())? {
+                    println!("{}", share::grid(&app));
+                }
+                return Ok(());
+            }
+
+            let saved = saved.or_else(|| saved_game::start(&app));
+            let settings = Settings::load();
+            let mut finished = None;
+            run_in_terminal(|terminal| {
+                if !settings.tutorial_done {
+                    if tutorial::run(terminal)? {
+                        // Nothing was played, there is nothing to pick up.
+                        saved_game::discard();
+                        return Ok(());
+                    }
+                    // Showing the tutorial again is better than no game.
+                    let _ = Settings::save_tutorial_done();
+                }
+                finished = run_app(terminal, app, recorder, saved)?;
+                Ok(())
+            })?;
+            if let Some(signal) = signals::terminating() {
+                std::process::exit(128 + signal);
+            }
+            if let Some(app) = finished {
+                println!("{}", share::grid(&app));
+            }
+            Ok(())
+        }
+        Ok(Command::Bench(options)) => {
+            println!("{}", bench::run(&options));
+            Ok(())
+        }
+        Ok(Command::Hotseat) => run_in_terminal(hotseat::run),
+        Ok(Command::Endless) => run_in_terminal(endless::run),
+        Ok(Command::Multi(options)) => run_in_terminal(|terminal| multi::run(terminal, &options)),
+        Ok(Command::Host(options)) => {
+            let client = lan::host(&options)?;
+            run_in_terminal(|terminal| lan::run(terminal, client))
+        }
+        Ok(Command::Join(options)) => {
+            let client = lan::Client::connect(options.addr.as_str(), &options.name)?;
+            run_in_terminal(|terminal| lan::run(terminal, client))
+        }
+        Ok(Command::Replay(options)) => {
+            let replay = Replay::load(&options.path)?;
+            run_in_terminal(|terminal| replay::run(terminal, replay, options.speed))
+        }
+        Ok(Command::Code(puzzle)) => {
+            println!("{}", puzzle_code::encode(&puzzle));
+            Ok(())
+        }
+        Ok(Command::Stats) => {
+            print!("{}\n{}", Stats::load(), endless::HighScores::load());
+            Ok(())
+        }
+        Ok(Command::Keys) => {
+            let (key_bindings, errors) = KeyBindings::with_overrides(&Settings::load().key_bindings);
+            print!("{key_bindings}");
+            for err in errors {
+                eprintln!("warning: skipped key binding: {err}");
+            }
+            Ok(())
+        }
+        Ok(Command::Tutorial) => run_in_terminal(|terminal| tutorial::run(terminal).map(drop)),
+        Ok(Command::History(options)) => {
+            run_in_terminal(|terminal| history::run(terminal, options))
+        }
+        Ok(Command::EditMask(options)) => {
+            let saved = mask_editor::load(&options)?;
+            run_in_terminal(|terminal| mask_editor::run(terminal, options, saved))
+        }
+        Ok(Command::Help) => {
+            print!("{}", cli::USAGE);
+            Ok(())
+        }
+        Err(err) => {
+            eprint!("error: {err}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Sets up the terminal, hands it to `run` and restores it afterwards, even
+/// when `run` fails.
+fn run_in_terminal<F>(run: F) -> Result<(), Box<dyn Error>>
+where
+    F: FnOnce(&mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()>,
+{
+    // Signals are set up before the screen is taken over, so a failure there
+    // has nothing to undo.
+    signals::listen()?;
+
     // setup terminal
-    enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let terminal =
+        enter_screen(&mut stdout).and_then(|()| Terminal::new(CrosstermBackend::new(stdout)));
+    let mut terminal = match terminal {
+        Ok(terminal) => terminal,
+        Err(err) => {
+            let _ = leave_screen(&mut io::stdout());
+            return Err(err.into());
+        }
+    };
+
+    // A panic would otherwise leave its message on the alternate screen and
+    // the shell in raw mode.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = leave_screen(&mut io::stdout());
+        default_hook(info);
+    }));
 
-    // create app and run it
-    let app = App::default();
-    let res = run_app(&mut terminal, app);
+    let res = run(&mut terminal);
 
     // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    drop(panic::take_hook());
+    leave_screen(terminal.backend_mut())?;
 
     if let Err(err) = res {
         println!("{:?}", err)
@@ -322,101 +1025,299 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
+fn enter_screen<W: Write>(out: &mut W) -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(out, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)
+}
+
+fn leave_screen<W: Write>(out: &mut W) -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        out,
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        Show
+    )
+}
+
+/// The key presses an input event amounts to for `app`. A paste types its
+/// letters into the active row, as far as they fit, so it is recorded and
+/// replayed like typing.
+fn key_presses(app: &App, event: Event) -> Vec<KeyEvent> {
+    match event {
+        Event::Key(key) => vec![key],
+        Event::Paste(text) => {
+            let typing = app.state == GameState::InProgress
+                && !app.show_help
+                && app.hint_menu.is_none()
+                && app.current_guess < app.playable_rows();
+            if !typing {
+                return Vec::new();
+            }
+            text.chars()
+                .filter(char::is_ascii_alphabetic)
+                .take(5 - app.input.len())
+                .map(|letter| KeyEvent::from(KeyCode::Char(letter.to_ascii_lowercase())))
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Waits up to `timeout` for the next event. The suspend key stops the
+/// process here, for every screen, and coming back from a suspend clears the
+/// terminal. Both show up as a resize so the screen is drawn again whole.
+fn poll_event<B: Backend>(
+    terminal: &mut Terminal<B>,
+    key_bindings: &KeyBindings,
+    timeout: Duration,
+) -> io::Result<Option<Event>> {
+    if !signals::take_redraw() {
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+        match event::read()? {
+            Event::Key(key) if key_bindings.action(key) == Some(Action::Suspend) => {
+                signals::suspend();
+                signals::take_redraw();
+            }
+            event => return Ok(Some(event)),
+        }
+    }
+    terminal.clear()?;
+    let size = terminal.size()?;
+    Ok(Some(Event::Resize(size.width, size.height)))
+}
+
+/// Like [`poll_event`], for screens that only change on input.
+fn read_event<B: Backend>(
+    terminal: &mut Terminal<B>,
+    key_bindings: &KeyBindings,
+) -> io::Result<Event> {
+    loop {
+        if let Some(event) = poll_event(terminal, key_bindings, TICK_RATE)? {
+            return Ok(event);
+        }
+    }
+}
+
+/// Plays `app` until the player quits. Returns the game if it was finished,
+/// so its result can be shared. Every key also goes to `saved`, which is
+/// kept if the game is cut short by a signal, and dropped otherwise.
+fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut app: App,
+    mut recorder: Option<Recorder>,
+    mut saved: Option<Recorder>,
+) -> io::Result<Option<App>> {
+    let mut stats_recorded = false;
+
     loop {
         terminal.draw(|f| ui(f, &mut app))?;
+        if signals::terminating().is_some() {
+            return Ok(None);
+        }
 
-        if let Event::Key(key) = event::read()? {
-            match key.code {
-                KeyCode::Enter => {
-                    if valid_guess(app.input.clone()) {
-                        // This is purely for the keyboard
-                        for (char_idx, (correct_char, char)) in app
-                            .correct_word
-                            .clone()
-                            .chars()
-                            .zip(app.input.clone().chars()).enumerate()
-                        {
-                            if !app.mask.get_mask(app.current_guess, char_idx)  {
-                                if correct_char == char {
-                                    app.set_letter_state(char, CharacterState::Correct);
-                                }
-                            }
-                        }
-
-                        for (char_idx, char) in app.input.clone().chars().enumerate() {
-                            if !app.mask.get_mask(app.current_guess, char_idx)  {
-                                if app.get_letter_state(char) == CharacterState::Unknown {
-                                    if app.correct_word.contains(char) {
-                                        app.set_letter_state(char, CharacterState::WrongPlace);
-                                    } else {
-                                        app.set_letter_state(char, CharacterState::NotInWord);
-                                    }
-                                }
-                            } 
-                        }
-
-                        if app.correct_word == app.input.as_str() {
-                            app.state = GameState::Won;
-                        }
-
-                        app.guesses[app.current_guess] = Row::from_current(&mut app);
-
-                        app.current_guess += 1;
-
-                        if app.current_guess == 5 && app.state != GameState::Won {
-                            app.state = GameState::Lost(app.correct_word.clone());
-                        }
-                    }
+        if let Some(event) = poll_event(terminal, &app.key_bindings, TICK_RATE)? {
+            for key in key_presses(&app, event) {
+                if let Some(recorder) = recorder.as_mut() {
+                    recorder.record(key)?;
                 }
-                KeyCode::Char(c) => {
-                    if app.state == GameState::InProgress {
-                        if c != ' ' && app.input.len() < 5 && app.current_guess < 5 {
-                            app.input.push(c.to_ascii_lowercase());
-                        }
-                    } else {
-                        if c == 'q' {
-                            return Ok(());
-                        }
-                    }
+                if let Some(saved) = saved.as_mut() {
+                    // Losing the save only matters if the game is cut short.
+                    let _ = saved.record(key);
                 }
-                KeyCode::Backspace => {
-                    app.input.pop();
-                }
-                KeyCode::Esc => {
-                    return Ok(());
+
+                if handle_key(&mut app, key).is_break() {
+                    saved_game::discard();
+                    return Ok(stats_recorded.then_some(app));
                 }
-                _ => {}
+            }
+        }
+        app.tick();
+
+        if app.state != GameState::InProgress && !stats_recorded {
+            record_stats(&mut app);
+            stats_recorded = true;
+            if saved.take().is_some() {
+                saved_game::discard();
             }
         }
     }
 }
 
-fn valid_guess(s: String) -> bool {
-    if s.len() == 5 {
-        true
-    } else {
-        false
+/// Adds a finished game to the saved stats.
+fn record_stats(app: &mut App) {
+    let mut stats = Stats::load();
+    stats.record(app);
+    // Losing the stats file is not worth interrupting the game over.
+    match stats.save().and_then(|()| history::record(app)) {
+        Ok(()) => app.toasts.push(Severity::Success, "Stats saved"),
+        Err(_) => app.toasts.push(Severity::Warning, "Couldn't save your stats"),
     }
 }
 
+/// Applies a single key press to the game. Returns `ControlFlow::Break` when
+/// the key asks to quit.
+fn handle_key(app: &mut App, key: KeyEvent) -> ControlFlow<()> {
+    if app.state == GameState::InProgress {
+        if let Some(timer) = app.timer.as_mut() {
+            timer.start();
+        }
+    }
+
+    let action = app.key_bindings.action(key);
+    if action == Some(Action::Quit) {
+        return ControlFlow::Break(());
+    }
+    if app.show_help {
+        app.show_help = false;
+        return ControlFlow::Continue(());
+    }
+    if let Some(menu) = app.hint_menu {
+        hints::handle_key(app, menu, key);
+        return ControlFlow::Continue(());
+    }
+
+    match action {
+        Some(Action::Help) => app.show_help = true,
+        Some(Action::Hint) if app.state == GameState::InProgress => hints::open(app),
+        Some(Action::Submit) if app.state == GameState::InProgress && valid_guess(&app.input) => {
+            let row = Row::from_current(app);
+            // This is purely for the keyboard
+            app.update_key_status(&row);
+            app.guesses[app.current_guess] = row;
+            if let Some(previous) = app.current_guess.checked_sub(1) {
+                app.reveal_delayed(previous);
+            }
+            if app.guesses[app.current_guess].guess == app.correct_word {
+                app.state = GameState::Won;
+            }
+
+            app.current_guess += 1;
+
+            if app.current_guess == app.playable_rows() && app.state != GameState::Won {
+                app.state = GameState::Lost(app.correct_word.clone());
+            }
+            if app.state != GameState::InProgress {
+                app.reveal_delayed(app.current_guess - 1);
+            }
+
+            if let Some(timer) = app.timer.as_mut() {
+                if app.state == GameState::InProgress {
+                    timer.next_guess();
+                } else {
+                    timer.stop();
+                }
+            }
+        }
+        Some(Action::Submit) if app.state == GameState::InProgress => {
+            app.toasts.push(Severity::Warning, "Not enough letters");
+        }
+        Some(Action::DeleteLetter) => {
+            app.input.pop();
+        }
+        Some(Action::ClearWord) => app.input.clear(),
+        Some(Action::Back) => return ControlFlow::Break(()),
+        Some(_) => {}
+        // Unbound shortcuts like alt+x don't type their letter.
+        None if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {}
+        None => match key.code {
+            KeyCode::Char(c) if app.state == GameState::InProgress => {
+                let fits = app.input.len() < 5 && app.current_guess < app.playable_rows();
+                if c != ' ' && fits {
+                    app.input.push(c.to_ascii_lowercase());
+                }
+            }
+            KeyCode::Char('q') => return ControlFlow::Break(()),
+            KeyCode::Char('r') => app.reveal_masked = !app.reveal_masked,
+            _ => {}
+        },
+    }
+
+    ControlFlow::Continue(())
+}
+
+fn valid_guess(s: &str) -> bool {
+    s.len() == 5
+}
+
+const MAX_GUESSES: usize = 5;
+const TICK_RATE: Duration = Duration::from_millis(100);
+const LOW_TIME_WARNING: Duration = Duration::from_secs(10);
 const ROWS: usize = 6;
 const COLUMNS: usize = 5;
 const CELL_WIDTH: usize = 5;
 const CELL_HEIGHT: usize = 3;
 const PADDING: usize = 1;
+/// Rows around the board for the margins, header and keyboard.
+const BOARD_SURROUNDINGS_HEIGHT: usize = 18;
+
+/// How big the cells of the board are drawn, picked with `cell_size` in the
+/// settings or else the biggest that fits the terminal.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CellSize {
+    /// One character, the size the help and mask editor always use.
+    Small,
+    /// One character with room around it.
+    Medium,
+    /// A letter of the block font.
+    Large,
+}
+
+impl CellSize {
+    const LARGEST_FIRST: [CellSize; 3] = [CellSize::Large, CellSize::Medium, CellSize::Small];
+
+    fn width(self) -> usize {
+        match self {
+            CellSize::Small => CELL_WIDTH,
+            CellSize::Medium => 7,
+            CellSize::Large => font::WIDTH + 4,
+        }
+    }
+
+    fn height(self) -> usize {
+        match self {
+            CellSize::Small => CELL_HEIGHT,
+            CellSize::Medium => 5,
+            CellSize::Large => font::HEIGHT + 2,
+        }
+    }
+
+    /// The biggest size whose board still leaves room for the header and
+    /// keyboard in `area`.
+    fn fit(area: Rect) -> Self {
+        CellSize::LARGEST_FIRST
+            .into_iter()
+            .find(|size| {
+                let width = size.width() * COLUMNS + 2 * PADDING + 2;
+                let height = size.height() * ROWS + 2 * PADDING + BOARD_SURROUNDINGS_HEIGHT;
+                width <= area.width as usize && height <= area.height as usize
+            })
+            .unwrap_or(CellSize::Small)
+    }
+}
 
 fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App) {
-    let terminal_rect = frame.size();
-    let grid_width = (CELL_WIDTH * COLUMNS + 2 * PADDING) as u16;
-    let grid_height = (CELL_HEIGHT * ROWS + 2 * PADDING) as u16;
+    let (area, status_area) = status_bar::split(frame.size());
+    draw_game(frame, app, area);
+    if let Some(status_area) = status_area {
+        status_bar::draw(frame, &app.theme, &StatusBar::for_game(app), status_area);
+    }
+}
 
-    let row_constraints = std::iter::repeat(Constraint::Length((CELL_HEIGHT) as u16))
-        .take(ROWS)
+/// Draws the header, board and keyboard of one game centered in `area`.
+fn draw_game<B: Backend>(frame: &mut Frame<B>, app: &mut App, area: Rect) {
+    let size = app.cell_size.unwrap_or_else(|| CellSize::fit(area));
+    let grid_width = (size.width() * COLUMNS + 2 * PADDING) as u16;
+    let grid_height = (size.height() * ROWS + 2 * PADDING) as u16;
+
+    let row_constraints = std::iter::repeat_n(Constraint::Length(size.height() as u16), ROWS)
         .collect::<Vec<_>>();
 
-    let col_constraints = std::iter::repeat(Constraint::Length(CELL_WIDTH as u16))
-        .take(COLUMNS)
+    let col_constraints = std::iter::repeat_n(Constraint::Length(size.width() as u16), COLUMNS)
         .collect::<Vec<_>>();
 
     let outer_rects = Layout::default()
@@ -424,11 +1325,11 @@ fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App) {
         .vertical_margin(1)
         .horizontal_margin(1)
         .constraints(vec![Constraint::Min(grid_height)])
-        .split(frame.size());
+        .split(area);
 
     let game_rectangle = outer_rects[0];
 
-    let horizontal_pad_block_width = (terminal_rect.width - grid_width) / 2;
+    let horizontal_pad_block_width = area.width.saturating_sub(grid_width) / 2;
     let center_center_horizontally = Layout::default()
         .direction(Direction::Horizontal)
         .constraints(vec![
@@ -438,7 +1339,7 @@ fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App) {
         ])
         .split(game_rectangle);
 
-    let vertical_pad_block_height = (game_rectangle.height - grid_height) / 2;
+    let vertical_pad_block_height = game_rectangle.height.saturating_sub(grid_height) / 2;
     let center_content_vertically = Layout::default()
         .direction(Direction::Vertical)
         .constraints(vec![
@@ -485,6 +1386,11 @@ fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App) {
             .constraints(col_constraints.clone())
             .split(row);
 
+        if let Some(hint) = app.guesses[row_index].hint {
+            render_hint_row(frame, app, size, hint, chunks);
+            continue;
+        }
+
         let row_state = if row_index == app.current_guess {
             RowState::Current
         } else if row_index > app.current_guess {
@@ -494,27 +1400,42 @@ fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App) {
         };
 
         match row_state {
-            RowState::Current => render_active_row(frame, app, chunks),
-            RowState::Empty => render_empty_row(frame, app, chunks, row_index),
-            RowState::AlreadyGuessed => render_already_guessed_row(frame, app, row_index, chunks),
+            RowState::Current => render_active_row(frame, app, size, chunks),
+            RowState::Empty => render_empty_row(frame, app, size, chunks, row_index),
+            RowState::AlreadyGuessed => {
+                render_already_guessed_row(frame, app, size, row_index, chunks)
+            }
         }
     }
+
+    if app.show_help {
+        help::draw(frame, &app.theme, &app.key_bindings, area);
+    }
 }
 
-fn render_empty_row<B: Backend>(frame: &mut Frame<B>, app: &mut App, cell_chunks: Vec<Rect>, row_index: usize) -> () {
+fn render_empty_row<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &mut App,
+    size: CellSize,
+    cell_chunks: Vec<Rect>,
+    row_index: usize,
+) {
     for (idx, cell_chunk) in cell_chunks.into_iter().enumerate() {
         let brightness = if app.guesses[row_index].char_states[idx] == CharacterState::Masked {
             Modifier::REVERSED
         } else {
             Modifier::empty()
         };
+        let kind = app.mask.get_mask(row_index, idx);
         let content = render_cell_with_text_and_colors(
-            ' ',
+            kind.placeholder(),
+            size,
             BlockTheme {
                 border_color: app.theme.empty_row_block_color,
                 text_color: app.theme.empty_row_block_color,
-                border_thickness: app.theme.row_border_thickness,
+                border_thickness: kind.border(app.theme.row_border_thickness),
                 border_brightness: brightness,
+                corner: None,
             },
         );
 
@@ -525,15 +1446,14 @@ fn render_empty_row<B: Backend>(frame: &mut Frame<B>, app: &mut App, cell_chunks
 fn render_active_row<B: Backend>(
     frame: &mut Frame<B>,
     app: &mut App,
+    size: CellSize,
     cell_chunks: Vec<Rect>,
-) -> () {
+) {
     let mut chars = app.input.chars();
 
     for (idx, cell_chunk) in cell_chunks.into_iter().enumerate() {
-        let text = match chars.next() {
-            Some(l) => l,
-            _ => ' ',
-        };
+        let kind = app.mask.get_mask(app.current_guess, idx);
+        let text = chars.next().unwrap_or(kind.placeholder());
         let brightness = if app.guesses[app.current_guess].char_states[idx] == CharacterState::Masked {
             Modifier::REVERSED
         } else {
@@ -541,11 +1461,13 @@ fn render_active_row<B: Backend>(
         };
         let content = render_cell_with_text_and_colors(
             text,
+            size,
             BlockTheme {
                 border_color: app.theme.border_color,
                 text_color: app.theme.active_row_input_color,
-                border_thickness: app.theme.row_border_thickness,
+                border_thickness: kind.border(app.theme.row_border_thickness),
                 border_brightness: brightness,
+                corner: None,
             },
         );
         frame.render_widget(content, cell_chunk);
@@ -555,51 +1477,148 @@ fn render_active_row<B: Backend>(
 fn render_already_guessed_row<B: Backend>(
     frame: &mut Frame<B>,
     app: &mut App,
+    size: CellSize,
     row_index: usize,
     chunks: Vec<Rect>,
-) -> () {
+) {
     if let Some(word_guess) = app.guesses.get(row_index) {
         let items = chunks.iter().zip(word_guess.chars());
 
         for (char_id, (chunk, character)) in items.enumerate() {
             let accuracy = app.guesses[row_index].char_states[char_id];
-
-            let color = match accuracy {
-                CharacterState::Correct => app.theme.guess_in_right_place_color,
-                CharacterState::WrongPlace => app.theme.guess_in_word_color,
-                CharacterState::NotInWord => app.theme.guess_not_in_word_color,
-                CharacterState::Unknown => app.theme.keyboard_not_guessed_color,
-                CharacterState::Masked => app.theme.active_row_input_color
-            };
-
-            let brightness = match accuracy {
-                CharacterState::WrongPlace => Modifier::DIM,
-                CharacterState::Masked => Modifier::REVERSED,
-                _ => Modifier::empty(),
+            let content = match app.revealed_state(row_index, char_id) {
+                Some(revealed) => revealed_cell(&app.theme, size, character, revealed),
+                None => guessed_cell(&app.theme, size, character, accuracy),
             };
+            frame.render_widget(content, *chunk);
+        }
+    }
+}
 
-            let content = render_cell_with_text_and_colors(
-                character,
-                BlockTheme {
-                    border_color: color,
-                    text_color: color,
-                    border_thickness: app.theme.guessed_row_border_thickness,
-                    border_brightness: brightness,
-                },
-            );
+/// A submitted cell showing the feedback it got.
+fn guessed_cell(
+    theme: &Theme,
+    size: CellSize,
+    character: char,
+    accuracy: CharacterState,
+) -> Paragraph<'static> {
+    let color = theme.guess_color(accuracy);
+
+    let brightness = match accuracy {
+        CharacterState::WrongPlace | CharacterState::LetterHidden(Shade::WrongPlace) => {
+            Modifier::DIM
+        }
+        CharacterState::Masked => Modifier::REVERSED,
+        CharacterState::Delayed => Modifier::DIM,
+        _ => Modifier::empty(),
+    };
 
-            frame.render_widget(content, *chunk);
+    let (character, border_thickness) = match accuracy {
+        CharacterState::InWord => (character, BorderType::Rounded),
+        CharacterState::Delayed => (character, BorderType::Double),
+        CharacterState::LetterHidden(_) => {
+            (CellKind::LetterHidden.symbol(), theme.guessed_row_border_thickness)
         }
+        _ => (character, theme.guessed_row_border_thickness),
+    };
+
+    render_cell_with_text_and_colors(
+        character,
+        size,
+        BlockTheme {
+            border_color: color,
+            text_color: color,
+            border_thickness,
+            border_brightness: brightness,
+            corner: theme.marker(accuracy),
+        },
+    )
+}
+
+/// A masked cell showing the feedback it hid. Revealed cells keep a thin
+/// border and dim text, so they can't be mistaken for feedback the player
+/// actually got.
+fn revealed_cell(
+    theme: &Theme,
+    size: CellSize,
+    character: char,
+    revealed: CharacterState,
+) -> Paragraph<'static> {
+    let color = theme.guess_color(revealed);
+    render_cell_with_text_and_colors(
+        character,
+        size,
+        BlockTheme {
+            border_color: color,
+            text_color: color,
+            border_thickness: theme.row_border_thickness,
+            border_brightness: Modifier::DIM | Modifier::ITALIC,
+            corner: theme.marker(revealed),
+        },
+    )
+}
+
+/// A row spent on a hint, showing what the hint revealed.
+fn render_hint_row<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &App,
+    size: CellSize,
+    hint: Hint,
+    chunks: Vec<Rect>,
+) {
+    let (column, text, state) = match hint {
+        Hint::Unmask { column } => (column, CellKind::Hidden.symbol(), None),
+        Hint::Letter { letter, in_word } => (
+            0,
+            letter,
+            Some(if in_word {
+                CharacterState::InWord
+            } else {
+                CharacterState::NotInWord
+            }),
+        ),
+        Hint::Position { column, letter } => (column, letter, Some(CharacterState::Correct)),
+    };
+
+    for (idx, chunk) in chunks.into_iter().enumerate() {
+        let content = match state {
+            _ if idx != column => hint_cell(size, ' ', app.theme.hint_color, None),
+            Some(state) => {
+                hint_cell(size, text, app.theme.guess_color(state), app.theme.marker(state))
+            }
+            None => hint_cell(size, text, app.theme.hint_color, None),
+        };
+        frame.render_widget(content, chunk);
     }
 }
 
-fn render_cell_with_text_and_colors(text: char, block_theme: BlockTheme) -> Paragraph<'static> {
-    let text = formatted_cell_text(text);
+fn hint_cell(size: CellSize, text: char, color: Color, corner: Option<char>) -> Paragraph<'static> {
+    render_cell_with_text_and_colors(
+        text,
+        size,
+        BlockTheme {
+            border_color: color,
+            text_color: color,
+            border_thickness: BorderType::Plain,
+            border_brightness: Modifier::DIM,
+            corner,
+        },
+    )
+}
+
+fn render_cell_with_text_and_colors(
+    text: char,
+    size: CellSize,
+    block_theme: BlockTheme,
+) -> Paragraph<'static> {
+    let text = formatted_cell_text(text, size);
+    let corner = block_theme.corner.map(String::from).unwrap_or_default();
 
     Paragraph::new(text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .title(corner)
                 .border_type(block_theme.border_thickness)
                 .border_style(Style::default().fg(block_theme.border_color))
                 .style(
@@ -612,35 +1631,97 @@ fn render_cell_with_text_and_colors(text: char, block_theme: BlockTheme) -> Para
         .style(Style::default().fg(block_theme.text_color))
 }
 
+/// The part of `rect` inside `area`, or `None` if none of it is.
+/// `Rect::intersection` underflows for rects past the bottom or right edge.
+fn clip(rect: Rect, area: Rect) -> Option<Rect> {
+    rect.intersects(area).then(|| rect.intersection(area))
+}
+
 // This is taken directly from the minesweeper app
 // https://github.com/cpcloud/minesweep-rs/blob/main/src/ui.rs
-fn formatted_cell_text(text: char) -> String {
-    let single_row_text = format!("{:^length$}", text, length = CELL_WIDTH - 2);
-    let pad_line = " ".repeat(CELL_WIDTH);
-    let num_pad_lines = CELL_HEIGHT - 3;
-
-    std::iter::repeat(pad_line.clone())
-        .take(num_pad_lines / 2)
-        .chain(std::iter::once(single_row_text.clone()))
-        .chain(std::iter::repeat(pad_line).take(num_pad_lines / 2))
+fn formatted_cell_text(text: char, size: CellSize) -> String {
+    let lines = match size {
+        CellSize::Large => font::glyph(text),
+        _ => None,
+    }
+    .unwrap_or_else(|| vec![format!("{:^length$}", text, length = size.width() - 2)]);
+    let pad_line = " ".repeat(size.width());
+    let num_pad_lines = (size.height() - 2).saturating_sub(lines.len());
+
+    std::iter::repeat_n(pad_line.clone(), num_pad_lines / 2)
+        .chain(lines)
+        .chain(std::iter::repeat_n(pad_line, num_pad_lines / 2))
         .collect::<Vec<_>>()
         .join("\n")
 }
 
+/// How to leave a finished game: q, or whatever goes back from the board.
+fn exit_prompt(key_bindings: &KeyBindings) -> String {
+    match key_bindings.key_for(Action::Back) {
+        Some(key) => format!("Press q or {key} key to exit."),
+        None => String::from("Press q key to exit."),
+    }
+}
+
 fn draw_header<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rect) {
-    let text = match &app.state {
-        GameState::Won => String::from("Game is over! You win! Press q or esc key to exit."),
-        GameState::Lost(answer) => {
-            format!("Game over! The answer was '{answer}'. Press q or esc key to exit.")
+    let toast = app
+        .toasts
+        .current()
+        .map(|toast| (toast.text.clone(), toast.severity));
+    let exit = exit_prompt(&app.key_bindings);
+    let text = match (&app.state, &app.timer) {
+        (GameState::Won, Some(timer)) => format!(
+            "You win in {}! {exit}",
+            timer::format_duration(timer.elapsed())
+        ),
+        (GameState::Won, None) => format!("Game is over! You win! {exit}"),
+        (GameState::Lost(answer), Some(timer)) if timer.remaining() == Some(Duration::ZERO) => {
+            format!("Time's up! The answer was '{answer}'. {exit}")
+        }
+        (GameState::Lost(answer), _) => {
+            format!("Game over! The answer was '{answer}'. {exit}")
+        }
+        (GameState::InProgress, _) if app.hint_menu.is_some() => {
+            let cancel = app.key_bindings.key_for(Action::Back);
+            app.hint_menu.map_or(String::new(), |menu| menu.prompt(cancel))
         }
-        GameState::InProgress => String::from(""),
+        (GameState::InProgress, Some(timer)) => timer.display(),
+        (GameState::InProgress, None) if app.current_guess == 0 => app
+            .key_bindings
+            .key_for(Action::Help)
+            .map_or(String::new(), |key| format!("Press {key} for help")),
+        (GameState::InProgress, None) => String::from(""),
+    };
+    // The key hint goes in the title, the text is already full at game end.
+    let title = match app.reveal_masked {
+        _ if app.state == GameState::InProgress || !app.has_masked_feedback() => app.title.clone(),
+        false => format!("{} (r: reveal)", app.title),
+        true => format!("{} (r: as played)", app.title),
     };
 
-    let header_text_color = match &app.state {
-        GameState::Won => app.theme.header_text_success_color,
+    let header_text_color = match (&app.state, app.timer.as_ref().and_then(Timer::remaining)) {
+        (GameState::Won, _) => app.theme.header_text_success_color,
+        (GameState::InProgress, Some(remaining)) if remaining <= LOW_TIME_WARNING => {
+            app.theme.header_text_error_color
+        }
+        (GameState::InProgress, _) => app.theme.border_color,
         _ => app.theme.header_text_error_color,
     };
 
+    // Messages show over everything but a menu waiting for its answer.
+    let (text, header_text_color) = match toast {
+        Some((toast, severity)) if app.hint_menu.is_none() => {
+            let color = match severity {
+                Severity::Info => app.theme.border_color,
+                Severity::Success => app.theme.header_text_success_color,
+                Severity::Warning => app.theme.header_text_warning_color,
+                Severity::Error => app.theme.header_text_error_color,
+            };
+            (toast, color)
+        }
+        _ => (text, header_text_color),
+    };
+
     let header_text = Paragraph::new(text)
         .wrap(Wrap { trim: true })
         .style(Style::default().fg(header_text_color))
@@ -649,7 +1730,7 @@ fn draw_header<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .style(Style::default().fg(app.theme.border_color))
-                .title("Spotle Tui")
+                .title(title)
                 .border_type(BorderType::Plain),
         );
 
@@ -657,7 +1738,7 @@ fn draw_header<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rect) {
 }
 
 fn draw_keyboard<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rect) {
-    let keyboard_key_rows = vec!["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+    let keyboard_key_rows = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
     let keyboard_spans = keyboard_key_rows
         .iter()
         .fold(vec![], |mut acc, keyboard_row| {
@@ -665,11 +1746,10 @@ fn draw_keyboard<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rect) {
             // except for the last character, so that we don't go off-center
             let letters: Vec<Span> = keyboard_row
                 .chars()
-                .into_iter()
                 .enumerate()
                 .map(|(letter_index, letter)| {
                     let use_offset = letter_index != keyboard_row.len() - 1;
-                    keyboard_letter(&app, letter, use_offset)
+                    keyboard_letter(app, letter, use_offset)
                 })
                 .collect();
 
@@ -698,9 +1778,9 @@ fn keyboard_letter<'a>(app: &'a App, le: char, use_offset: bool) -> Span<'a> {
     let color = match key_state {
         Unknown => app.theme.keyboard_not_guessed_color,
         Correct => app.theme.keyboard_in_right_place_color,
-        WrongPlace => app.theme.keyboard_in_word_color,
+        WrongPlace | InWord => app.theme.keyboard_in_word_color,
         NotInWord => app.theme.keyboard_not_in_word_color,
-        Masked => app.theme.active_row_input_color
+        Masked | Delayed | LetterHidden(_) => app.theme.active_row_input_color,
     };
 
     let display_modifier = match key_state {
@@ -708,9 +1788,12 @@ fn keyboard_letter<'a>(app: &'a App, le: char, use_offset: bool) -> Span<'a> {
         _ => Modifier::empty(),
     };
 
-    let key_string = match use_offset {
-        true => format!("{le} "),
-        false => le.to_string(),
+    // Markers take the place of the gap after each key, so the last key gets
+    // one too.
+    let key_string = match app.theme.marker(key_state) {
+        Some(marker) => format!("{le}{marker}"),
+        None if use_offset || app.theme.markers => format!("{le} "),
+        None => le.to_string(),
     };
 
     Span::styled(
@@ -718,3 +1801,77 @@ fn keyboard_letter<'a>(app: &'a App, le: char, use_offset: bool) -> Span<'a> {
         Style::default().fg(color).add_modifier(display_modifier),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui::backend::TestBackend;
+    use CharacterState::*;
+
+    /// What a test terminal shows, one line per row.
+    pub fn screen(terminal: &Terminal<TestBackend>) -> String {
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol.as_str()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn scores_each_cell() {
+        let table = [
+            ("crane", "crane", 0, Correct),
+            ("crane", "nacre", 0, WrongPlace),
+            ("crane", "ghost", 2, NotInWord),
+            // A repeated letter is in the word wherever it is guessed.
+            ("crane", "eerie", 1, WrongPlace),
+        ];
+        for (answer, guess, char_idx, expected) in table {
+            assert_eq!(score_cell(answer, guess, char_idx), expected, "{guess} at {char_idx}");
+        }
+    }
+
+    #[test]
+    fn scores_each_cell_kind() {
+        let table = [
+            (".....", "cloud", [Correct, NotInWord, NotInWord, NotInWord, NotInWord]),
+            ("#....", "cloud", [Masked, NotInWord, NotInWord, NotInWord, NotInWord]),
+            ("???..", "nacre", [InWord, InWord, InWord, WrongPlace, Correct]),
+            ("??...", "cloud", [InWord, NotInWord, NotInWord, NotInWord, NotInWord]),
+            ("~~...", "cloud", [Delayed, Delayed, NotInWord, NotInWord, NotInWord]),
+            (
+                "***..",
+                "cadet",
+                [
+                    LetterHidden(Shade::Correct),
+                    LetterHidden(Shade::WrongPlace),
+                    LetterHidden(Shade::NotInWord),
+                    WrongPlace,
+                    NotInWord,
+                ],
+            ),
+        ];
+        for (mask, guess, expected) in table {
+            let mask = Mask::parse(mask).unwrap();
+            assert_eq!(score_guess("crane", guess, &mask, 0), expected, "{guess} under {mask}");
+        }
+    }
+
+    fn submit(app: &mut App, word: &str) {
+        for key in word.chars().map(KeyCode::Char).chain([KeyCode::Enter]) {
+            let _ = handle_key(app, KeyEvent::new(key, KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn delayed_cells_are_revealed_by_the_next_row() {
+        let mut app = App::new("crane", Mask::parse("~?.../~?.../.....").unwrap());
+        submit(&mut app, "carve");
+        assert_eq!(app.guesses[0].char_states[..2], [Delayed, InWord]);
+        submit(&mut app, "ghost");
+        assert_eq!(app.guesses[0].char_states[..2], [Correct, InWord]);
+        assert_eq!(app.guesses[1].char_states[..2], [Delayed, NotInWord]);
+    }
+}
@@ -0,0 +1,472 @@
+use crate::{
+    clip, draw_game, handle_key,
+    keys::{Action, KeyBindings},
+    read_event, render_cell_with_text_and_colors,
+    settings::Settings,
+    solver::Solver,
+    words::ANSWERS,
+    App, BlockTheme, CellKind, CellSize, GameState, Mask, Theme, CELL_HEIGHT, CELL_WIDTH,
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use std::io;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+const DEFAULT_DENSITY: f64 = 0.2;
+const DENSITY_STEP: f64 = 0.05;
+
+pub struct EditorOptions {
+    /// Saved mask to open, which is also the name it is saved back under.
+    pub name: Option<String>,
+}
+
+enum PromptKind {
+    SaveAs,
+    Preview,
+}
+
+struct Prompt {
+    kind: PromptKind,
+    text: String,
+}
+
+struct Editor {
+    mask: Mask,
+    cursor: (usize, usize),
+    density: f64,
+    name: Option<String>,
+    dirty: bool,
+    prompt: Option<Prompt>,
+    /// The built-in solver's game against a chosen word, shown instead of the
+    /// grid until the next key press.
+    preview: Option<App>,
+    message: Option<String>,
+    theme: Theme,
+    key_bindings: KeyBindings,
+    /// Where each cell was last drawn, so mouse clicks can be mapped to cells.
+    cell_rects: Vec<((usize, usize), Rect)>,
+}
+
+impl Editor {
+    fn rows(&self) -> usize {
+        self.mask.items.len() / 5
+    }
+
+    /// Moves the cell on to the next kind, wrapping back to visible.
+    fn cycle(&mut self, (row, column): (usize, usize)) {
+        let cell = &mut self.mask.items[row * 5 + column];
+        let next = CellKind::ALL.iter().position(|kind| kind == cell).unwrap_or(0) + 1;
+        *cell = CellKind::ALL[next % CellKind::ALL.len()];
+        self.dirty = true;
+    }
+
+    fn move_cursor(&mut self, row_delta: isize, column_delta: isize) {
+        let (row, column) = self.cursor;
+        self.cursor = (
+            row.saturating_add_signed(row_delta).min(self.rows() - 1),
+            column.saturating_add_signed(column_delta).min(4),
+        );
+    }
+
+    fn randomize(&mut self) {
+        self.mask = Mask::random(&mut rand::thread_rng(), self.density, self.rows());
+        self.dirty = true;
+    }
+
+    fn save(&mut self, name: String) {
+        match self.mask.save(&name) {
+            Ok(path) => {
+                self.message = Some(format!("Saved to {}", path.display()));
+                self.name = Some(name);
+                self.dirty = false;
+            }
+            Err(err) => self.message = Some(err),
+        }
+    }
+
+    fn preview(&mut self, word: &str) {
+        if word.len() == 5 {
+            self.preview = Some(solver_game(word, &self.mask));
+        } else {
+            self.message = Some(String::from("Preview words need five letters"));
+        }
+    }
+
+    /// Handles a key press while a prompt is open.
+    fn prompt_key(&mut self, mut prompt: Prompt, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                let text = prompt.text;
+                match prompt.kind {
+                    PromptKind::SaveAs => self.save(text),
+                    PromptKind::Preview => self.preview(&text),
+                }
+                return;
+            }
+            KeyCode::Esc => return,
+            KeyCode::Backspace => {
+                prompt.text.pop();
+            }
+            KeyCode::Char(c) => match prompt.kind {
+                PromptKind::SaveAs if c.is_ascii_alphanumeric() || c == '-' || c == '_' => {
+                    prompt.text.push(c)
+                }
+                PromptKind::Preview if c.is_ascii_alphabetic() && prompt.text.len() < 5 => {
+                    prompt.text.push(c.to_ascii_lowercase())
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        self.prompt = Some(prompt);
+    }
+}
+
+/// Plays the built-in solver against `answer` on `mask`, to show what the
+/// mask hides in a real game.
+fn solver_game(answer: &str, mask: &Mask) -> App {
+    let mut app = App::new(answer, mask.clone());
+    app.title = format!("Solver vs '{answer}'");
+    app.key_bindings = KeyBindings::default();
+    let mut solver = Solver::new(ANSWERS);
+
+    while app.state == GameState::InProgress {
+        let Some(guess) = solver.next_guess() else {
+            break;
+        };
+        let row_idx = app.current_guess;
+        app.input = guess.to_string();
+        let _ = handle_key(&mut app, KeyEvent::from(KeyCode::Enter));
+        solver.apply(guess, &app.guesses[row_idx].char_states, mask, row_idx);
+    }
+    app
+}
+
+/// Reads the saved mask the editor opens, or `None` for a new one. A mask
+/// that is there but can't be read is an error, so saving over it has to be
+/// done on purpose.
+pub fn load(options: &EditorOptions) -> Result<Option<Mask>, String> {
+    let Some(name) = &options.name else {
+        return Ok(None);
+    };
+    match Mask::load(name) {
+        Ok(mask) => Ok(Some(mask)),
+        Err(_) if Mask::path(name)?.try_exists().is_ok_and(|exists| !exists) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Edits `saved`, as read by [`load`].
+pub fn run<B: Backend>(
+    terminal: &mut Terminal<B>,
+    options: EditorOptions,
+    saved: Option<Mask>,
+) -> io::Result<()> {
+    let (mask, message) = match (saved, &options.name) {
+        (Some(mask), _) => (mask, None),
+        (None, Some(name)) => (Mask::default(), Some(format!("New mask '{name}'"))),
+        (None, None) => (Mask::default(), None),
+    };
+    let settings = Settings::load();
+    let mut editor = Editor {
+        mask,
+        cursor: (0, 0),
+        density: DEFAULT_DENSITY,
+        name: options.name,
+        dirty: false,
+        prompt: None,
+        preview: None,
+        message,
+        theme: Theme::detect(&settings),
+        key_bindings: KeyBindings::with_overrides(&settings.key_bindings).0,
+        cell_rects: Vec::new(),
+    };
+
+    loop {
+        terminal.draw(|f| ui(f, &mut editor))?;
+
+        match read_event(terminal, &editor.key_bindings)? {
+            // Quit leaves at once, like everywhere else, unsaved or not.
+            Event::Key(key) if editor.key_bindings.action(key) == Some(Action::Quit) => {
+                return Ok(());
+            }
+            Event::Key(_) if editor.preview.is_some() => editor.preview = None,
+            Event::Key(key) => {
+                if let Some(prompt) = editor.prompt.take() {
+                    editor.prompt_key(prompt, key);
+                    continue;
+                }
+
+                let quitting = matches!(key.code, KeyCode::Char('q'))
+                    || editor.key_bindings.action(key) == Some(Action::Back);
+                if !quitting {
+                    editor.message = None;
+                }
+                match key.code {
+                    _ if quitting => {
+                        if !editor.dirty || editor.message.is_some() {
+                            return Ok(());
+                        }
+                        editor.message =
+                            Some(String::from("Unsaved changes, press q again to quit"));
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => editor.move_cursor(-1, 0),
+                    KeyCode::Down | KeyCode::Char('j') => editor.move_cursor(1, 0),
+                    KeyCode::Left | KeyCode::Char('h') => editor.move_cursor(0, -1),
+                    KeyCode::Right | KeyCode::Char('l') => editor.move_cursor(0, 1),
+                    KeyCode::Char(' ') | KeyCode::Enter => editor.cycle(editor.cursor),
+                    KeyCode::Char('r') => editor.randomize(),
+                    KeyCode::Char('+') | KeyCode::Char('=') => {
+                        editor.density = (editor.density + DENSITY_STEP).min(1.0);
+                    }
+                    KeyCode::Char('-') => {
+                        editor.density = (editor.density - DENSITY_STEP).max(0.0);
+                    }
+                    KeyCode::Char('c') => {
+                        editor.mask = Mask {
+                            items: vec![CellKind::Visible; editor.rows() * 5],
+                        };
+                        editor.dirty = true;
+                    }
+                    KeyCode::Char('i') => {
+                        for cell in editor.mask.items.iter_mut() {
+                            *cell = match *cell {
+                                CellKind::Visible => CellKind::Hidden,
+                                CellKind::Hidden => CellKind::Visible,
+                                kind => kind,
+                            };
+                        }
+                        editor.dirty = true;
+                    }
+                    KeyCode::Char('p') => {
+                        editor.prompt = Some(Prompt {
+                            kind: PromptKind::Preview,
+                            text: String::new(),
+                        });
+                    }
+                    KeyCode::Char('s') => match editor.name.clone() {
+                        Some(name) => editor.save(name),
+                        None => {
+                            editor.prompt = Some(Prompt {
+                                kind: PromptKind::SaveAs,
+                                text: String::new(),
+                            });
+                        }
+                    },
+                    KeyCode::Char('n') => {
+                        editor.prompt = Some(Prompt {
+                            kind: PromptKind::SaveAs,
+                            text: editor.name.clone().unwrap_or_default(),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                ..
+            }) if editor.preview.is_none() && editor.prompt.is_none() => {
+                let clicked = editor.cell_rects.iter().find(|(_, rect)| {
+                    (rect.left()..rect.right()).contains(&column)
+                        && (rect.top()..rect.bottom()).contains(&row)
+                });
+                if let Some((cell, _)) = clicked.copied() {
+                    editor.cursor = cell;
+                    editor.cycle(cell);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn ui<B: Backend>(frame: &mut Frame<B>, editor: &mut Editor) {
+    let area = frame.size();
+
+    if let Some(preview) = editor.preview.as_mut() {
+        draw_game(frame, preview, area);
+        let hint = Paragraph::new("Preview of the built-in solver. Press any key to go back.")
+            .alignment(Alignment::Center)
+            .style(Style::default().add_modifier(Modifier::DIM));
+        frame.render_widget(
+            hint,
+            Rect::new(area.x, area.y, area.width, 1.min(area.height)),
+        );
+        return;
+    }
+
+    let grid_width = (CELL_WIDTH * 5 + 2) as u16;
+    let grid_height = (CELL_HEIGHT * editor.rows() + 2) as u16;
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(vec![
+            Constraint::Length(3),
+            Constraint::Length(grid_height),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    let columns = |chunk: Rect| {
+        let width = grid_width.max(40).min(chunk.width);
+        Rect::new(chunk.x + (chunk.width - width) / 2, chunk.y, width, chunk.height)
+    };
+
+    draw_header(frame, editor, columns(sections[0]));
+    draw_grid(frame, editor, sections[1]);
+    draw_help(frame, editor, columns(sections[2]));
+}
+
+fn draw_header<B: Backend>(frame: &mut Frame<B>, editor: &Editor, chunk: Rect) {
+    let title = match &editor.name {
+        Some(name) if editor.dirty => format!("Mask Editor - {name}*"),
+        Some(name) => format!("Mask Editor - {name}"),
+        None => String::from("Mask Editor"),
+    };
+    let text = match (&editor.prompt, &editor.message) {
+        (
+            Some(Prompt {
+                kind: PromptKind::SaveAs,
+                text,
+            }),
+            _,
+        ) => format!("Save as: {text}_"),
+        (
+            Some(Prompt {
+                kind: PromptKind::Preview,
+                text,
+            }),
+            _,
+        ) => {
+            format!("Preview against word: {text}_")
+        }
+        (None, Some(message)) => message.clone(),
+        (None, None) => {
+            let masked = editor
+                .mask
+                .items
+                .iter()
+                .filter(|kind| **kind != CellKind::Visible)
+                .count();
+            format!("{masked} of {} cells masked", editor.mask.items.len())
+        }
+    };
+
+    let header = Paragraph::new(text)
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default().fg(editor.theme.border_color))
+                .title(title)
+                .border_type(BorderType::Plain),
+        );
+    frame.render_widget(header, chunk);
+}
+
+fn draw_grid<B: Backend>(frame: &mut Frame<B>, editor: &mut Editor, chunk: Rect) {
+    let grid_width = (CELL_WIDTH * 5 + 2) as u16;
+    let left = chunk.x + chunk.width.saturating_sub(grid_width) / 2;
+    let grid = Rect::new(left, chunk.y, grid_width.min(chunk.width), chunk.height);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+        grid,
+    );
+
+    editor.cell_rects.clear();
+    for row in 0..editor.rows() {
+        for column in 0..5 {
+            let cell = Rect::new(
+                grid.x + 1 + (column * CELL_WIDTH) as u16,
+                grid.y + 1 + (row * CELL_HEIGHT) as u16,
+                CELL_WIDTH as u16,
+                CELL_HEIGHT as u16,
+            );
+            // Rows past the bottom of a short terminal are left out.
+            let Some(rect) = clip(cell, frame.size()) else {
+                continue;
+            };
+
+            let at_cursor = editor.cursor == (row, column);
+            let color = if at_cursor {
+                editor.theme.guess_in_word_color
+            } else {
+                editor.theme.empty_row_block_color
+            };
+            let kind = editor.mask.get_mask(row, column);
+            let content = render_cell_with_text_and_colors(
+                match kind.placeholder() {
+                    ' ' if at_cursor => '+',
+                    placeholder => placeholder,
+                },
+                CellSize::Small,
+                BlockTheme {
+                    border_color: color,
+                    text_color: color,
+                    border_thickness: kind.border(editor.theme.row_border_thickness),
+                    border_brightness: if kind == CellKind::Hidden {
+                        Modifier::REVERSED
+                    } else {
+                        Modifier::empty()
+                    },
+                    corner: None,
+                },
+            );
+            frame.render_widget(content, rect);
+            editor.cell_rects.push(((row, column), rect));
+        }
+    }
+}
+
+fn draw_help<B: Backend>(frame: &mut Frame<B>, editor: &Editor, chunk: Rect) {
+    let text = format!(
+        "arrows move, space/click cycle visible, # hidden, ? binary, ~ delayed, \
+         * letter hidden, r randomize, +/- density ({:.0}%), c clear, i invert, \
+         p preview, s save, n save as, q quit",
+        editor.density * 100.0
+    );
+    let help = Paragraph::new(text)
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center)
+        .style(Style::default().add_modifier(Modifier::DIM));
+    frame.render_widget(help, chunk);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui::backend::TestBackend;
+
+    #[test]
+    fn draws_on_a_short_terminal() {
+        let mut editor = Editor {
+            mask: Mask::default(),
+            cursor: (0, 0),
+            density: DEFAULT_DENSITY,
+            name: None,
+            dirty: false,
+            prompt: None,
+            preview: None,
+            message: None,
+            theme: Theme::default(),
+            key_bindings: KeyBindings::default(),
+            cell_rects: Vec::new(),
+        };
+        let mut terminal = Terminal::new(TestBackend::new(80, 16)).unwrap();
+        terminal.draw(|f| ui(f, &mut editor)).unwrap();
+        assert!(!editor.cell_rects.is_empty());
+        assert!(editor.cell_rects.len() < editor.mask.items.len());
+        let screen = crate::tests::screen(&terminal);
+        assert!(screen.contains("Mask Editor"), "{screen}");
+        assert!(screen.contains("5 of 25 cells masked"), "{screen}");
+    }
+}
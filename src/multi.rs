@@ -0,0 +1,395 @@
+use crate::{
+    clip, exit_prompt, handle_key, key_presses,
+    keys::Action,
+    read_event,
+    status_bar::{self, StatusBar},
+    words, App, CellKind, CharacterState, GameState, Mask, MAX_GUESSES,
+};
+use crossterm::event::KeyCode;
+use rand::seq::SliceRandom;
+use std::io;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+pub const BOARD_COUNTS: [usize; 3] = [2, 4, 8];
+const MASK_DENSITY: f64 = 0.2;
+const BOARDS_PER_ROW: usize = 4;
+const COMPACT_CELL_WIDTH: u16 = 3;
+const BOARD_WIDTH: u16 = COMPACT_CELL_WIDTH * 5 + 2;
+const HEADER_HEIGHT: u16 = 3;
+
+pub struct MultiOptions {
+    pub boards: usize,
+}
+
+/// Several boards played at once: every guess goes to each board that is not
+/// solved yet. Each board has its own answer and mask, and one extra row per
+/// board on top of the usual `MAX_GUESSES`.
+struct MultiGame {
+    boards: Vec<App>,
+}
+
+impl MultiGame {
+    fn new(count: usize) -> Self {
+        let guesses = count + MAX_GUESSES;
+        let mut rng = rand::thread_rng();
+        let answers = words::ANSWERS
+            .choose_multiple(&mut rng, count)
+            .copied()
+            .collect::<Vec<_>>();
+
+        let boards = answers
+            .into_iter()
+            .map(|answer| {
+                let mask = Mask::random(&mut rng, MASK_DENSITY, guesses);
+                App::with_guesses(answer, mask, guesses)
+            })
+            .collect();
+        Self { boards }
+    }
+
+    fn state(&self) -> GameState {
+        if self.boards.iter().all(|board| board.state == GameState::Won) {
+            GameState::Won
+        } else if self
+            .boards
+            .iter()
+            .any(|board| matches!(board.state, GameState::Lost(_)))
+        {
+            let answers = self
+                .boards
+                .iter()
+                .map(|board| format!("'{}'", board.correct_word))
+                .collect::<Vec<_>>();
+            GameState::Lost(answers.join(", "))
+        } else {
+            GameState::InProgress
+        }
+    }
+
+    /// The row the next guess goes into, counted across all boards.
+    fn current_guess(&self) -> usize {
+        self.boards
+            .iter()
+            .map(|board| board.current_guess)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn max_guesses(&self) -> usize {
+        self.boards[0].guesses.len()
+    }
+}
+
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, options: &MultiOptions) -> io::Result<()> {
+    let mut game = MultiGame::new(options.boards);
+
+    loop {
+        terminal.draw(|f| ui(f, &game))?;
+
+        // Every board types the same input, any board still playing will do.
+        let typing = game
+            .boards
+            .iter()
+            .find(|board| board.state == GameState::InProgress)
+            .unwrap_or(&game.boards[0]);
+        for key in key_presses(typing, read_event(terminal, &typing.key_bindings)?) {
+            let in_progress = game.state() == GameState::InProgress;
+            match (game.boards[0].key_bindings.action(key), key.code) {
+                (Some(Action::Quit | Action::Back), _) => return Ok(()),
+                (_, KeyCode::Char('q')) if !in_progress => return Ok(()),
+                // Hints pay with a row, which boards can't share, and help is
+                // drawn by the board, which this screen doesn't show whole.
+                (Some(Action::Hint | Action::Help), _) => {}
+                _ if in_progress => {
+                    for board in game
+                        .boards
+                        .iter_mut()
+                        .filter(|board| board.state == GameState::InProgress)
+                    {
+                        let _ = handle_key(board, key);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn ui<B: Backend>(frame: &mut Frame<B>, game: &MultiGame) {
+    let (area, status_area) = status_bar::split(frame.size());
+    let theme = &game.boards[0].theme;
+    if let Some(status_area) = status_area {
+        status_bar::draw(frame, theme, &status(game), status_area);
+    }
+
+    let count = game.boards.len();
+    let columns = count.min(BOARDS_PER_ROW) as u16;
+    let board_rows = count.div_ceil(BOARDS_PER_ROW) as u16;
+    let board_height = game.max_guesses() as u16 + 2;
+    let status_lines = status_lines(count);
+    let keyboard_height = 3 * status_lines + 2;
+
+    let key_width = (count as u16 / status_lines) + 2;
+    let boards_width = columns * (BOARD_WIDTH + 1) - 1;
+    let content_width = boards_width.max(10 * key_width + 1);
+    let content_height = HEADER_HEIGHT + board_rows * board_height + keyboard_height;
+    let left = area.x + area.width.saturating_sub(content_width) / 2;
+    let boards_left = left + (content_width - boards_width) / 2;
+    let top = area.y + area.height.saturating_sub(content_height) / 2;
+    // Boards and keyboard that don't fit are left out rather than squeezed.
+    let clip = |rect: Rect| clip(rect, area);
+
+    if let Some(chunk) = clip(Rect::new(left, top, content_width, HEADER_HEIGHT)) {
+        draw_header(frame, game, chunk);
+    }
+
+    for (idx, board) in game.boards.iter().enumerate() {
+        let column = (idx % BOARDS_PER_ROW) as u16;
+        let row = (idx / BOARDS_PER_ROW) as u16;
+        let chunk = Rect::new(
+            boards_left + column * (BOARD_WIDTH + 1),
+            top + HEADER_HEIGHT + row * board_height,
+            BOARD_WIDTH,
+            board_height,
+        );
+        if let Some(chunk) = clip(chunk) {
+            frame.render_widget(compact_board(board), chunk);
+        }
+    }
+
+    let keyboard_chunk = Rect::new(
+        left,
+        top + HEADER_HEIGHT + board_rows * board_height,
+        content_width,
+        keyboard_height,
+    );
+    let keyboard = Paragraph::new(split_keyboard(game))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default().fg(theme.border_color))
+                .title("Available Letters")
+                .border_type(BorderType::Plain),
+        );
+    if let Some(chunk) = clip(keyboard_chunk) {
+        frame.render_widget(keyboard, chunk);
+    }
+}
+
+/// The keys of the multi board screen, which has no help or hints.
+fn status(game: &MultiGame) -> StatusBar {
+    let count = game.boards.len();
+    let solved = game
+        .boards
+        .iter()
+        .filter(|board| board.state == GameState::Won)
+        .count();
+    let bar = StatusBar::new("Multi").info(format!("{solved}/{count} solved"));
+    if game.state() != GameState::InProgress {
+        return bar.key("q", "quit");
+    }
+
+    let bindings = &game.boards[0].key_bindings;
+    let mut bar = bar.key("a-z", "type");
+    for (action, what) in [
+        (Action::Submit, "submit"),
+        (Action::DeleteLetter, "delete"),
+        (Action::Back, "quit"),
+    ] {
+        if let Some(key) = bindings.key_for(action) {
+            bar = bar.key(key.to_string(), what);
+        }
+    }
+    bar.progress(format!("{}/{}", game.current_guess() + 1, game.max_guesses()))
+}
+
+fn draw_header<B: Backend>(frame: &mut Frame<B>, game: &MultiGame, chunk: Rect) {
+    let theme = &game.boards[0].theme;
+    let count = game.boards.len();
+    let exit = exit_prompt(&game.boards[0].key_bindings);
+
+    let (text, color) = match game.state() {
+        GameState::Won => (
+            format!("You solved all {count} boards! {exit}"),
+            theme.header_text_success_color,
+        ),
+        GameState::Lost(answers) => (
+            format!("Game over! The answers were {answers}. {exit}"),
+            theme.header_text_error_color,
+        ),
+        GameState::InProgress => (
+            format!("Guess {}/{}", game.current_guess() + 1, game.max_guesses()),
+            theme.border_color,
+        ),
+    };
+
+    let header = Paragraph::new(text)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(color))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default().fg(theme.border_color))
+                .title(format!("Spotle Tui - {count} boards"))
+                .border_type(BorderType::Plain),
+        );
+    frame.render_widget(header, chunk);
+}
+
+/// Renders a board with one line per row and colored cells instead of the
+/// bordered cells of the single board view, so several boards fit on screen.
+fn compact_board(board: &App) -> Paragraph<'static> {
+    let theme = &board.theme;
+    let in_progress = board.state == GameState::InProgress;
+
+    let lines = board
+        .guesses
+        .iter()
+        .enumerate()
+        .map(|(row_idx, row)| {
+            let cells = (0..5).map(|char_idx| {
+                let masked = board.mask.get_mask(row_idx, char_idx) == CellKind::Hidden;
+                let mut marker = ' ';
+                let (letter, style) = if row_idx < board.current_guess {
+                    let state = row.char_states[char_idx];
+                    marker = theme.marker(state).unwrap_or(' ');
+                    let letter = match state {
+                        CharacterState::LetterHidden(_) => CellKind::LetterHidden.symbol(),
+                        _ => row.guess.chars().nth(char_idx).unwrap_or(' '),
+                    };
+                    let style = match state {
+                        CharacterState::Unknown
+                        | CharacterState::Masked
+                        | CharacterState::Delayed => Style::default(),
+                        _ => Style::default()
+                            .bg(theme.guess_color(state))
+                            .fg(Color::Black)
+                            .add_modifier(Modifier::BOLD),
+                    };
+                    (letter, style)
+                } else if row_idx == board.current_guess && in_progress {
+                    let letter = board.input.chars().nth(char_idx).unwrap_or(' ');
+                    (letter, Style::default().fg(theme.active_row_input_color))
+                } else {
+                    ('·', Style::default().fg(theme.empty_row_block_color))
+                };
+
+                let style = if masked {
+                    style.add_modifier(Modifier::REVERSED)
+                } else {
+                    style
+                };
+                Span::styled(format!(" {}{marker}", letter.to_ascii_uppercase()), style)
+            });
+            Spans::from(cells.collect::<Vec<_>>())
+        })
+        .collect::<Vec<_>>();
+
+    let border_color = match board.state {
+        GameState::Won => theme.guess_in_right_place_color,
+        _ => theme.border_color,
+    };
+    Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(border_color)),
+    )
+}
+
+/// How many lines of per-board markers each key takes up.
+fn status_lines(boards: usize) -> u16 {
+    if boards > BOARDS_PER_ROW {
+        2
+    } else {
+        1
+    }
+}
+
+/// The keyboard with one colored marker per board next to every letter, laid
+/// out in the same order as the boards above it.
+fn split_keyboard(game: &MultiGame) -> Vec<Spans<'static>> {
+    let theme = &game.boards[0].theme;
+    let status_lines = status_lines(game.boards.len()) as usize;
+    let key_width = game.boards.len() / status_lines;
+
+    let mut lines = Vec::new();
+    for keyboard_row in ["qwertyuiop", "asdfghjkl", "zxcvbnm"] {
+        for status_line in 0..status_lines {
+            let boards = &game.boards[status_line * key_width..(status_line + 1) * key_width];
+            let mut spans = Vec::new();
+            for letter in keyboard_row.chars() {
+                let label = if status_line == 0 { letter } else { ' ' };
+                spans.push(Span::styled(
+                    label.to_string(),
+                    Style::default().fg(theme.keyboard_not_guessed_color),
+                ));
+                spans.extend(boards.iter().map(|board| key_marker(board, letter)));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Spans::from(spans));
+        }
+    }
+    lines
+}
+
+fn key_marker(board: &App, letter: char) -> Span<'static> {
+    let theme = &board.theme;
+    if board.state == GameState::Won {
+        return Span::raw(" ");
+    }
+
+    let state = board.get_letter_state(letter);
+    let (text, style) = match state {
+        CharacterState::Correct => ("▀", Style::default().fg(theme.keyboard_in_right_place_color)),
+        CharacterState::WrongPlace | CharacterState::InWord => {
+            ("▀", Style::default().fg(theme.keyboard_in_word_color))
+        }
+        CharacterState::NotInWord => (
+            "▀",
+            Style::default()
+                .fg(theme.keyboard_not_in_word_color)
+                .add_modifier(Modifier::DIM),
+        ),
+        CharacterState::Unknown
+        | CharacterState::Masked
+        | CharacterState::Delayed
+        | CharacterState::LetterHidden(_) => (
+            "·",
+            Style::default()
+                .fg(theme.keyboard_not_guessed_color)
+                .add_modifier(Modifier::DIM),
+        ),
+    };
+
+    match theme.marker(state) {
+        Some(marker) => Span::styled(marker.to_string(), style),
+        None => Span::styled(text, style),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui::backend::TestBackend;
+
+    #[test]
+    fn eight_boards_render_on_a_small_terminal() {
+        let game = MultiGame::new(8);
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| ui(f, &game)).unwrap();
+        let screen = crate::tests::screen(&terminal);
+        assert!(screen.contains("Spotle Tui - 8 boards"), "{screen}");
+        assert!(screen.contains("Guess 1/"), "{screen}");
+        assert_eq!(screen.matches('╭').count(), 8, "{screen}");
+    }
+}
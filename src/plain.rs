@@ -0,0 +1,235 @@
+//! Line-oriented play without the terminal UI: guesses are read from stdin one
+//! per line and every result is printed as text, so the game works with
+//! screen readers and from scripts.
+
+use crate::{
+    handle_key,
+    hints::Hint,
+    keys::KeyBindings,
+    record_stats,
+    replay::Recorder,
+    App, CellKind, CharacterState, GameState, Shade,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+Type a five letter guess and press Enter. Each letter of the result is
+green (right place), yellow (in the word, elsewhere), grey (not in the word),
+hidden (masked, no feedback), in word (in the word or not, position unknown),
+pending (shown after the next guess) or hidden letter (feedback without the
+letter).
+Commands:
+    /board            Read out every row so far
+    /keys             List the letters by what is known about them
+    /hint unmask N    Unmask cell N of your next guess
+    /hint letter X    Test whether X is in the word
+    /hint position    Reveal the first unsolved position
+                      Every hint is paid for with your last row
+    /help             Show this help
+    /quit             Give up";
+
+/// Plays `app` reading lines from `input`. Returns the game if it was
+/// finished, like the terminal UI does.
+pub fn run<R: BufRead, W: Write>(
+    mut app: App,
+    mut recorder: Option<Recorder>,
+    input: R,
+    mut output: W,
+) -> io::Result<Option<App>> {
+    // The keys pressed below are the defaults, whatever the settings say.
+    app.key_bindings = KeyBindings::default();
+    writeln!(output, "{}. Type /help for commands.", app.title)?;
+    write_prompt(&app, &mut output)?;
+
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        let mut press = |app: &mut App, code: KeyCode| -> io::Result<()> {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.record(key)?;
+            }
+            let _ = handle_key(app, key);
+            Ok(())
+        };
+
+        match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [] => continue,
+            ["/quit"] => return Ok(None),
+            ["/help"] => writeln!(output, "{HELP}")?,
+            ["/board"] => write_board(&app, &mut output)?,
+            ["/keys"] => write_keys(&app, &mut output)?,
+            ["/hint", args @ ..] => {
+                let keys = match args {
+                    ["unmask", column] => ['1', column.chars().next().unwrap_or(' ')],
+                    ["letter", letter] => ['2', letter.chars().next().unwrap_or(' ')],
+                    ["position"] => ['3', ' '],
+                    _ => {
+                        writeln!(output, "Usage: /hint unmask N, /hint letter X or /hint position")?;
+                        continue;
+                    }
+                };
+                let hints = app.hints().count();
+                press(&mut app, KeyCode::Tab)?;
+                for key in keys {
+                    if app.hint_menu.is_some() {
+                        press(&mut app, KeyCode::Char(key))?;
+                    }
+                }
+                match app.hint_menu.take() {
+                    Some(_) => writeln!(output, "That hint needs a cell from 1 to 5 or a letter.")?,
+                    None if app.hints().count() > hints => {
+                        if let Some(hint) = app.hints().last() {
+                            writeln!(output, "Hint: {}", describe_hint(hint))?;
+                        }
+                    }
+                    None => {}
+                }
+            }
+            [guess] if guess.len() == 5 && guess.chars().all(|c| c.is_ascii_alphabetic()) => {
+                let before = app.guesses.clone();
+                for letter in guess.chars() {
+                    press(&mut app, KeyCode::Char(letter))?;
+                }
+                press(&mut app, KeyCode::Enter)?;
+                app.tick();
+
+                let guessed = app.current_guess - 1;
+                // Delayed cells of the row before show up with this guess.
+                if let Some(previous) = guessed.checked_sub(1) {
+                    if app.guesses[previous].char_states != before[previous].char_states {
+                        writeln!(output, "Guess {} now: {}", previous + 1, describe_row(&app, previous))?;
+                    }
+                }
+                writeln!(output, "Guess {}: {}", guessed + 1, describe_row(&app, guessed))?;
+            }
+            _ => writeln!(output, "Guesses are five letters, type /help for commands.")?,
+        }
+        for toast in app.toasts.drain() {
+            writeln!(output, "{}", toast.text)?;
+        }
+
+        app.tick();
+        match &app.state {
+            GameState::Won => {
+                writeln!(output, "You win in {} guesses!", app.current_guess)?;
+                record_stats(&mut app);
+                return Ok(Some(app));
+            }
+            GameState::Lost(answer) => {
+                writeln!(output, "Game over! The answer was '{answer}'.")?;
+                record_stats(&mut app);
+                return Ok(Some(app));
+            }
+            GameState::InProgress => write_prompt(&app, &mut output)?,
+        }
+        output.flush()?;
+    }
+
+    Ok(None)
+}
+
+/// Says which guess is next and which of its cells are masked.
+fn write_prompt<W: Write>(app: &App, output: &mut W) -> io::Result<()> {
+    let cells = (0..5)
+        .filter_map(|idx| {
+            let kind = match app.mask.get_mask(app.current_guess, idx) {
+                CellKind::Visible => return None,
+                CellKind::Hidden => "hidden",
+                CellKind::Binary => "in word or not",
+                CellKind::Delayed => "delayed",
+                CellKind::LetterHidden => "letter hidden",
+            };
+            Some(format!("{} {kind}", idx + 1))
+        })
+        .collect::<Vec<_>>();
+    let mask = match cells.is_empty() {
+        true => String::from("all cells visible"),
+        false => format!("cell {}", cells.join(", cell ")),
+    };
+    writeln!(
+        output,
+        "Guess {} of {}, {mask}:",
+        app.current_guess + 1,
+        app.playable_rows()
+    )?;
+    output.flush()
+}
+
+fn write_board<W: Write>(app: &App, output: &mut W) -> io::Result<()> {
+    if app.current_guess == 0 && app.hints().next().is_none() {
+        return writeln!(output, "No guesses yet.");
+    }
+    for row_idx in 0..app.current_guess {
+        writeln!(output, "Guess {}: {}", row_idx + 1, describe_row(app, row_idx))?;
+    }
+    for hint in app.hints() {
+        writeln!(output, "Hint: {}", describe_hint(hint))?;
+    }
+    Ok(())
+}
+
+fn write_keys<W: Write>(app: &App, output: &mut W) -> io::Result<()> {
+    let groups = [
+        ("Green", CharacterState::Correct),
+        ("Yellow", CharacterState::WrongPlace),
+        ("Grey", CharacterState::NotInWord),
+        ("Unused", CharacterState::Unknown),
+    ];
+    for (name, state) in groups {
+        let letters = ('a'..='z')
+            .filter(|letter| app.get_letter_state(*letter) == state)
+            .map(|letter| letter.to_ascii_uppercase().to_string())
+            .collect::<Vec<_>>();
+        if !letters.is_empty() {
+            writeln!(output, "{name}: {}", letters.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads out a submitted row, e.g. "W green, O yellow, R hidden, L grey, D grey".
+fn describe_row(app: &App, row_idx: usize) -> String {
+    let row = &app.guesses[row_idx];
+    row.guess
+        .chars()
+        .zip(row.char_states)
+        .map(|(letter, state)| {
+            let letter = letter.to_ascii_uppercase();
+            match state {
+                CharacterState::Correct => format!("{letter} green"),
+                CharacterState::WrongPlace => format!("{letter} yellow"),
+                CharacterState::NotInWord => format!("{letter} grey"),
+                CharacterState::Masked => format!("{letter} hidden"),
+                CharacterState::InWord => format!("{letter} in word"),
+                CharacterState::Delayed => format!("{letter} pending"),
+                CharacterState::Unknown => letter.to_string(),
+                CharacterState::LetterHidden(shade) => format!(
+                    "hidden letter {}",
+                    match shade {
+                        Shade::Correct => "green",
+                        Shade::WrongPlace => "yellow",
+                        Shade::NotInWord => "grey",
+                    }
+                ),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_hint(hint: Hint) -> String {
+    match hint {
+        Hint::Unmask { column } => format!("cell {} unmasked", column + 1),
+        Hint::Letter { letter, in_word: true } => {
+            format!("{} is in the word", letter.to_ascii_uppercase())
+        }
+        Hint::Letter { letter, in_word: false } => {
+            format!("{} is not in the word", letter.to_ascii_uppercase())
+        }
+        Hint::Position { column, letter } => {
+            format!("letter {} is {}", column + 1, letter.to_ascii_uppercase())
+        }
+    }
+}
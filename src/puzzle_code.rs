@@ -0,0 +1,238 @@
+//! Shareable codes for custom puzzles.
+//!
+//! A code is a format version digit followed by base32 symbols of five bits
+//! each: a two symbol salt, the answer (one symbol per letter), the number of
+//! mask rows, the mask and a two symbol checksum, which covers the version
+//! digit too. Everything after the salt is XORed with a keystream seeded by the
+//! salt, so the answer can't be read off the code and the same puzzle gives a
+//! different code each time it is shared.
+//!
+//! Format 1 packs each mask row into one symbol, a bit per hidden cell. Format
+//! 2 spends one symbol per cell so it can tell every [`CellKind`] apart, and is
+//! only used for masks that need it, so plain masks still give format 1 codes.
+
+use crate::{CellKind, Mask};
+use rand::Rng;
+
+/// Mask rows as one symbol each, a bit per hidden cell.
+const HIDDEN_BITS_VERSION: u8 = 1;
+/// Mask cells as one symbol each, the index of the cell's kind.
+const CELL_KINDS_VERSION: u8 = 2;
+/// Crockford's base32 alphabet, which leaves out I, L, O and U so codes are
+/// hard to mistype.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const SALT_SYMBOLS: usize = 2;
+const CHECKSUM_SYMBOLS: usize = 2;
+const GROUP_SIZE: usize = 4;
+
+pub struct Puzzle {
+    pub answer: String,
+    pub mask: Mask,
+}
+
+impl Puzzle {
+    pub fn new(answer: &str, mask: Mask) -> Result<Self, String> {
+        let answer = answer.to_ascii_lowercase();
+        if answer.len() != 5 || !answer.bytes().all(|letter| letter.is_ascii_lowercase()) {
+            return Err(format!("'{answer}' is not a five letter word"));
+        }
+        if mask.items.len() / 5 >= 32 {
+            return Err(String::from("masks in puzzle codes can have at most 31 rows"));
+        }
+        Ok(Self { answer, mask })
+    }
+}
+
+/// Turns `puzzle` into a code like `1K3F-0QZ7-M2XA-9TRB`.
+pub fn encode(puzzle: &Puzzle) -> String {
+    let mut payload = puzzle
+        .answer
+        .bytes()
+        .map(|letter| letter - b'a')
+        .collect::<Vec<_>>();
+    payload.push((puzzle.mask.items.len() / 5) as u8);
+    let plain = puzzle
+        .mask
+        .items
+        .iter()
+        .all(|kind| matches!(kind, CellKind::Visible | CellKind::Hidden));
+    let version = if plain {
+        payload.extend(puzzle.mask.items.chunks(5).map(|row| {
+            row.iter()
+                .fold(0, |bits, kind| (bits << 1) | u8::from(*kind == CellKind::Hidden))
+        }));
+        HIDDEN_BITS_VERSION
+    } else {
+        payload.extend(puzzle.mask.items.iter().map(|kind| {
+            CellKind::ALL.iter().position(|other| other == kind).unwrap_or(0) as u8
+        }));
+        CELL_KINDS_VERSION
+    };
+    seal(version, payload)
+}
+
+/// Checksums, salts and scrambles `payload` into a code of format `version`.
+fn seal(version: u8, mut payload: Vec<u8>) -> String {
+    payload.extend(checksum(version, &payload));
+
+    let salt = rand::thread_rng().gen_range(0..1 << (5 * SALT_SYMBOLS));
+    let mut symbols = vec![(salt >> 5) as u8, (salt & 0x1f) as u8];
+    symbols.extend(
+        payload
+            .iter()
+            .zip(keystream(salt))
+            .map(|(symbol, key)| symbol ^ key),
+    );
+
+    let mut code = String::from(ALPHABET[version as usize] as char);
+    for (idx, symbol) in symbols.iter().enumerate() {
+        if (idx + 1) % GROUP_SIZE == 0 {
+            code.push('-');
+        }
+        code.push(ALPHABET[*symbol as usize] as char);
+    }
+    code
+}
+
+/// Reads a code made by [`encode`]. Dashes, spaces and letter case are
+/// ignored, and the commonly confused `O`, `I` and `L` are read as `0` and `1`.
+pub fn decode(code: &str) -> Result<Puzzle, String> {
+    let symbols = code
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| {
+            let normalized = match c.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                other => other,
+            };
+            ALPHABET
+                .iter()
+                .position(|symbol| *symbol as char == normalized)
+                .map(|symbol| symbol as u8)
+                .ok_or(format!("'{c}' can't appear in a puzzle code"))
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+
+    let (version, symbols) = symbols.split_first().ok_or("the puzzle code is empty")?;
+    let corrupt = || String::from("the puzzle code is corrupt, check that it was copied completely and without typos");
+    if symbols.len() < SALT_SYMBOLS + CHECKSUM_SYMBOLS {
+        return Err(corrupt());
+    }
+
+    let (salt, payload) = symbols.split_at(SALT_SYMBOLS);
+    let salt = (u16::from(salt[0]) << 5) | u16::from(salt[1]);
+    let payload = payload
+        .iter()
+        .zip(keystream(salt))
+        .map(|(symbol, key)| symbol ^ key)
+        .collect::<Vec<_>>();
+
+    let (payload, expected_checksum) = payload.split_at(payload.len() - CHECKSUM_SYMBOLS);
+    if checksum(*version, payload) != expected_checksum {
+        return Err(corrupt());
+    }
+    // Only a code that checks out is from a newer format rather than mistyped.
+    if *version > CELL_KINDS_VERSION {
+        return Err(format!(
+            "this is a format {version} puzzle code, but this version of spotle_tui \
+             only reads format {HIDDEN_BITS_VERSION} and {CELL_KINDS_VERSION} codes"
+        ));
+    }
+    if *version < HIDDEN_BITS_VERSION || payload.len() < 6 {
+        return Err(corrupt());
+    }
+
+    let (answer, rest) = payload.split_at(5);
+    let (rows, mask_symbols) = rest.split_first().ok_or_else(corrupt)?;
+    let symbols_per_row = if *version == HIDDEN_BITS_VERSION { 1 } else { 5 };
+    if answer.iter().any(|letter| *letter >= 26)
+        || *rows as usize * symbols_per_row != mask_symbols.len()
+        || mask_symbols.is_empty()
+    {
+        return Err(corrupt());
+    }
+
+    let answer = answer.iter().map(|letter| (b'a' + letter) as char).collect();
+    let items = if *version == HIDDEN_BITS_VERSION {
+        mask_symbols
+            .iter()
+            .flat_map(|row| {
+                (0..5).rev().map(move |bit| match row & (1 << bit) {
+                    0 => CellKind::Visible,
+                    _ => CellKind::Hidden,
+                })
+            })
+            .collect()
+    } else {
+        mask_symbols
+            .iter()
+            .map(|kind| CellKind::ALL.get(*kind as usize).copied().ok_or_else(corrupt))
+            .collect::<Result<_, _>>()?
+    };
+    Ok(Puzzle {
+        answer,
+        mask: Mask { items },
+    })
+}
+
+/// Two symbol Fletcher checksum of the version and payload, so swapped symbols
+/// are caught as well as changed ones.
+fn checksum(version: u8, symbols: &[u8]) -> [u8; CHECKSUM_SYMBOLS] {
+    let (sum, sum_of_sums) = std::iter::once(&version).chain(symbols).fold((0u32, 0u32), |(sum, sum_of_sums), symbol| {
+        let sum = (sum + u32::from(*symbol)) % 31;
+        (sum, (sum_of_sums + sum) % 31)
+    });
+    [sum as u8, sum_of_sums as u8]
+}
+
+/// Five bit keys from a small xorshift generator seeded by the salt.
+fn keystream(salt: u16) -> impl Iterator<Item = u8> {
+    let mut state = 0x9e37_79b9u32 ^ u32::from(salt).wrapping_mul(0x85eb_ca6b);
+    std::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        (state >> 27) as u8
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code() -> String {
+        encode(&Puzzle::new("crane", Mask::default()).unwrap())
+    }
+
+    fn with_version(code: &str, version: char) -> String {
+        std::iter::once(version).chain(code.chars().skip(1)).collect()
+    }
+
+    #[test]
+    fn decodes_what_it_encodes() {
+        let puzzle = decode(&code()).unwrap();
+        assert_eq!(puzzle.answer, "crane");
+        assert_eq!(puzzle.mask.to_string(), Mask::default().to_string());
+    }
+
+    #[test]
+    fn only_codes_that_check_out_are_from_a_newer_format() {
+        let newer = decode(&seal(9, vec![0; 7])).err().unwrap();
+        assert!(newer.contains("format 9"), "{newer}");
+
+        let mut typo = seal(9, vec![0; 7]);
+        let last = typo.pop().unwrap();
+        typo.push(if last == '0' { '1' } else { '0' });
+        let corrupt = decode(&typo).err().unwrap();
+        assert!(corrupt.contains("corrupt"), "{corrupt}");
+    }
+
+    #[test]
+    fn a_mistyped_version_is_corrupt() {
+        for version in ['0', '2', '7', '9'] {
+            let mistyped = decode(&with_version(&code(), version)).err().unwrap();
+            assert!(mistyped.contains("corrupt"), "{version}: {mistyped}");
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use crate::{
+    draw_game, handle_key,
+    keys::{Action, KeyBindings},
+    poll_event,
+    settings::Settings,
+    status_bar::{self, StatusBar},
+    timer::{Timer, TimerMode},
+    App, GameState, Mask, TICK_RATE,
+};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use tui::{backend::Backend, Frame, Terminal};
+
+const REPLAY_VERSION: u32 = 1;
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 16.0;
+
+/// First line of a replay file, describing the puzzle that was played.
+#[derive(Serialize, Deserialize)]
+struct Header {
+    version: u32,
+    answer: String,
+    mask: String,
+    #[serde(default)]
+    adversarial: bool,
+    #[serde(default)]
+    timer: Option<TimerMode>,
+    /// The bindings the keys were pressed with, so they do the same when
+    /// played back under other settings.
+    #[serde(default)]
+    key_bindings: KeyBindings,
+}
+
+/// Every following line of a replay file: one key press and when it happened,
+/// in milliseconds since the game started.
+#[derive(Serialize, Deserialize)]
+struct RecordedKey {
+    at_ms: u64,
+    key: KeyEvent,
+}
+
+pub struct ReplayOptions {
+    pub path: PathBuf,
+    pub speed: f64,
+}
+
+/// Writes every key press of a game to a replay file as it happens, so the
+/// recording survives the game being killed.
+pub struct Recorder {
+    file: File,
+    started: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, app: &App) -> io::Result<Self> {
+        let mut recorder = Self {
+            file: File::create(path)?,
+            started: Instant::now(),
+        };
+        recorder.write_line(&Header {
+            version: REPLAY_VERSION,
+            answer: app.correct_word.clone(),
+            mask: app.mask.to_string(),
+            adversarial: app.adversary.is_some(),
+            timer: app.timer.as_ref().map(|timer| timer.mode),
+            key_bindings: app.key_bindings.clone(),
+        })?;
+        Ok(recorder)
+    }
+
+    pub fn record(&mut self, key: KeyEvent) -> io::Result<()> {
+        let at_ms = self.started.elapsed().as_millis() as u64;
+        self.write_line(&RecordedKey { at_ms, key })
+    }
+
+    /// Writes `value` and its newline in one go, so a crash can't leave half
+    /// a line followed by the next one.
+    fn write_line<T: Serialize>(&mut self, value: &T) -> io::Result<()> {
+        let mut line = serde_json::to_vec(value)?;
+        line.push(b'\n');
+        self.file.write_all(&line)
+    }
+}
+
+pub struct Replay {
+    answer: String,
+    mask: Mask,
+    adversarial: bool,
+    timer: Option<TimerMode>,
+    key_bindings: KeyBindings,
+    keys: Vec<RecordedKey>,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        let truncated = !content.ends_with('\n');
+        let mut lines = content.lines().peekable();
+
+        let header: Header = match lines.next() {
+            Some(line) => serde_json::from_str(line)?,
+            None => return Err(format!("{} is empty", path.display()).into()),
+        };
+        if header.version != REPLAY_VERSION {
+            return Err(format!(
+                "{} is a version {} replay, only version {REPLAY_VERSION} is supported",
+                path.display(),
+                header.version
+            )
+            .into());
+        }
+
+        let mut keys = Vec::new();
+        while let Some(line) = lines.next() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(key) => keys.push(key),
+                // The game was killed while writing its last key.
+                Err(_) if truncated && lines.peek().is_none() => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(Self {
+            answer: header.answer,
+            adversarial: header.adversarial,
+            timer: header.timer,
+            key_bindings: header.key_bindings,
+            mask: Mask::parse(&header.mask)?,
+            keys,
+        })
+    }
+
+    /// The recorded puzzle before any key was pressed.
+    fn new_game(&self) -> App {
+        let app = if self.adversarial {
+            App::adversarial(self.mask.clone())
+        } else {
+            App::new(&self.answer, self.mask.clone())
+        };
+        App {
+            key_bindings: self.key_bindings.clone(),
+            timer: self.timer.map(Timer::new),
+            ..app
+        }
+    }
+
+    /// Plays every recorded key into a fresh game at once, and records them
+    /// again to a new file at `path` so the game can go on from there. A
+    /// timed game's clock goes on from the last recorded key.
+    pub fn resume(self, path: &Path) -> io::Result<(App, Recorder)> {
+        let mut app = self.new_game();
+        let mut recorder = Recorder::create(path, &app)?;
+        let first_ms = self.keys.first().map_or(0, |key| key.at_ms);
+        let last_ms = self.keys.last().map_or(0, |key| key.at_ms);
+        let now = Instant::now();
+        recorder.started = now.checked_sub(Duration::from_millis(last_ms)).unwrap_or(now);
+        for recorded in self.keys {
+            recorder.write_line(&recorded)?;
+            let _ = handle_key(&mut app, recorded.key);
+        }
+        if let Some(timer) = app.timer.as_mut() {
+            if app.state == GameState::InProgress {
+                timer.resume_at(Duration::from_millis(last_ms - first_ms));
+            }
+        }
+        Ok((app, recorder))
+    }
+}
+
+/// Feeds the recorded keys into a fresh game on a virtual clock that runs at
+/// `speed` times real time.
+struct Player {
+    app: App,
+    /// The viewer's own bindings, for the playback controls.
+    key_bindings: KeyBindings,
+    keys: Vec<RecordedKey>,
+    next_key: usize,
+    /// When playback started, which the game's clock counts from.
+    started: Instant,
+    clock_ms: f64,
+    speed: f64,
+    paused: bool,
+}
+
+impl Player {
+    fn finished(&self) -> bool {
+        self.next_key == self.keys.len()
+    }
+
+    /// Real time left until the next recorded key is due.
+    fn time_until_next_key(&self) -> Option<Duration> {
+        if self.paused {
+            return None;
+        }
+        let key = self.keys.get(self.next_key)?;
+        let wait_ms = (key.at_ms as f64 - self.clock_ms).max(0.0) / self.speed;
+        // A timestamp too far off to wait for, from a damaged file, is
+        // treated like no key at all.
+        Duration::try_from_secs_f64(wait_ms / 1000.0).ok()
+    }
+
+    fn step(&mut self) {
+        if let Some(&RecordedKey { at_ms, key }) = self.keys.get(self.next_key) {
+            self.clock_ms = self.clock_ms.max(at_ms as f64);
+            // A limit that ran out before this key ends the game first, as it
+            // did when the game was played.
+            self.tick_at(at_ms);
+            // The recording ends with the key that quit the game, which would
+            // only close the replay before the final board can be seen.
+            let _ = handle_key(&mut self.app, key);
+            self.next_key += 1;
+        }
+    }
+
+    /// Runs the game's clock up to `at_ms` on the recording.
+    fn tick_at(&mut self, at_ms: u64) {
+        if let Some(timer) = self.app.timer.as_mut() {
+            timer.set_now(self.started + Duration::from_millis(at_ms));
+        }
+        self.app.tick();
+    }
+
+    fn catch_up(&mut self) {
+        while self
+            .keys
+            .get(self.next_key)
+            .is_some_and(|key| key.at_ms as f64 <= self.clock_ms)
+        {
+            self.step();
+        }
+        // The clock stops at the last key, so a game that was cut short
+        // doesn't run out of time after the recording ends.
+        let last_ms = self.keys.last().map_or(0, |key| key.at_ms);
+        self.tick_at((self.clock_ms as u64).min(last_ms));
+    }
+}
+
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, replay: Replay, speed: f64) -> io::Result<()> {
+    let mut player = Player {
+        app: replay.new_game(),
+        key_bindings: KeyBindings::with_overrides(&Settings::load().key_bindings).0,
+        keys: replay.keys,
+        next_key: 0,
+        started: Instant::now(),
+        clock_ms: 0.0,
+        speed: speed.clamp(MIN_SPEED, MAX_SPEED),
+        paused: false,
+    };
+    let mut last_tick = Instant::now();
+
+    loop {
+        terminal.draw(|f| ui(f, &mut player))?;
+
+        let timeout = player
+            .time_until_next_key()
+            .map_or(TICK_RATE, |wait| wait.min(TICK_RATE));
+        if let Some(Event::Key(key)) = poll_event(terminal, &player.key_bindings, timeout)? {
+            let action = player.key_bindings.action(key);
+            match key.code {
+                _ if matches!(action, Some(Action::Quit | Action::Back)) => return Ok(()),
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Char(' ') => player.paused = !player.paused,
+                KeyCode::Char('n') | KeyCode::Right => player.step(),
+                KeyCode::Char('+') | KeyCode::Up => {
+                    player.speed = (player.speed * 2.0).min(MAX_SPEED);
+                }
+                KeyCode::Char('-') | KeyCode::Down => {
+                    player.speed = (player.speed / 2.0).max(MIN_SPEED);
+                }
+                _ => {}
+            }
+        }
+
+        let now = Instant::now();
+        if !player.paused {
+            player.clock_ms += now.duration_since(last_tick).as_secs_f64() * 1000.0 * player.speed;
+        }
+        last_tick = now;
+        player.catch_up();
+    }
+}
+
+fn ui<B: Backend>(frame: &mut Frame<B>, player: &mut Player) {
+    let (area, status_area) = status_bar::split(frame.size());
+    draw_game(frame, &mut player.app, area);
+    let Some(status_area) = status_area else {
+        return;
+    };
+
+    let state = if player.finished() {
+        "finished"
+    } else if player.paused {
+        "paused"
+    } else {
+        "playing"
+    };
+    let bar = StatusBar::new("Replay")
+        .key("space", "pause")
+        .key("n", "step")
+        .key("+/-", "speed")
+        .key("q", "quit")
+        .info(format!("{state} at {}x", player.speed))
+        .progress(format!("key {}/{}", player.next_key, player.keys.len()));
+    status_bar::draw(frame, &player.app.theme, &bar, status_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_cut_off_last_line_is_dropped() {
+        let header = Header {
+            version: REPLAY_VERSION,
+            answer: String::from("crane"),
+            mask: Mask::default().to_string(),
+            adversarial: false,
+            timer: None,
+            key_bindings: KeyBindings::default(),
+        };
+        let key = RecordedKey {
+            at_ms: 10,
+            key: KeyEvent::from(KeyCode::Char('c')),
+        };
+        let line = serde_json::to_string(&key).unwrap();
+        let content = format!(
+            "{}\n{line}\n{}",
+            serde_json::to_string(&header).unwrap(),
+            &line[..line.len() / 2]
+        );
+
+        let path = std::env::temp_dir()
+            .join(format!("spotle_cut_off_{}.jsonl", std::process::id()));
+        fs::write(&path, &content).unwrap();
+        let loaded = Replay::load(&path);
+        fs::write(&path, content.replace(&line, "{")).unwrap();
+        let corrupt = Replay::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap().keys.len(), 1);
+        assert!(corrupt.is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//! The game in progress, kept on disk as a replay that grows with every key,
+//! so a game cut short by a signal or a crash picks up where it stopped.
+
+use crate::{
+    replay::{Recorder, Replay},
+    storage, App,
+};
+use std::{fs, path::PathBuf};
+
+const SAVED_GAME_FILE: &str = "saved_game.jsonl";
+
+fn path() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join(SAVED_GAME_FILE))
+}
+
+/// Starts saving `app`. Gives `None` if there is nowhere to save to, the
+/// game is still playable without it.
+pub fn start(app: &App) -> Option<Recorder> {
+    let path = path()?;
+    fs::create_dir_all(path.parent()?).ok()?;
+    Recorder::create(&path, app).ok()
+}
+
+/// Picks up the saved game, if there is one, and keeps saving it.
+pub fn resume() -> Option<(App, Recorder)> {
+    let path = path()?;
+    if !path.exists() {
+        return None;
+    }
+    // A save that can't be read is dropped rather than asked about forever.
+    match Replay::load(&path) {
+        Ok(replay) => replay.resume(&path).ok(),
+        Err(_) => {
+            discard();
+            None
+        }
+    }
+}
+
+/// Forgets the saved game once it is finished or given up.
+pub fn discard() {
+    if let Some(path) = path() {
+        let _ = fs::remove_file(path);
+    }
+}
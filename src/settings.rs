@@ -0,0 +1,99 @@
+use crate::{colors::ColorDepth, storage, CellSize, ThemeName};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, io};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Choices and progress that carry over between games.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Whether the first-run tutorial was played or skipped.
+    pub tutorial_done: bool,
+    /// Whether cells and keys show a marker next to their color.
+    pub markers: bool,
+    pub theme: ThemeName,
+    /// Overrides the colors the terminal is detected to support.
+    pub color_depth: Option<ColorDepth>,
+    /// Overrides picking the biggest cells that fit the terminal.
+    pub cell_size: Option<CellSize>,
+    /// Keys bound to something else than the default, e.g. `"f1": "help"`,
+    /// or to nothing with `null`.
+    /// Actions are read by [`crate::keys::KeyBindings::with_overrides`], so a
+    /// misspelled one is skipped instead of failing the whole file.
+    pub key_bindings: BTreeMap<String, Option<String>>,
+}
+
+impl Settings {
+    /// Reads every setting on its own, so one that can't be read, like a
+    /// theme that doesn't exist, falls back to its default without taking
+    /// the rest of the file with it.
+    pub fn load() -> Self {
+        Self::from_json(&storage::load(SETTINGS_FILE))
+    }
+
+    fn from_json(json: &Value) -> Self {
+        let mut settings = Self::default();
+        let Value::Object(fields) = json else {
+            return settings;
+        };
+        let Settings {
+            tutorial_done,
+            markers,
+            theme,
+            color_depth,
+            cell_size,
+            key_bindings,
+        } = &mut settings;
+        read(fields, "tutorial_done", tutorial_done);
+        read(fields, "markers", markers);
+        read(fields, "theme", theme);
+        read(fields, "color_depth", color_depth);
+        read(fields, "cell_size", cell_size);
+        read(fields, "key_bindings", key_bindings);
+        settings
+    }
+
+    /// Marks the tutorial as done in the settings file and leaves everything
+    /// else in it as the player wrote it. A file that isn't a JSON object is
+    /// left alone rather than replaced.
+    pub fn save_tutorial_done() -> io::Result<()> {
+        let mut json = match storage::read(SETTINGS_FILE)? {
+            Some(contents) => serde_json::from_str(&contents)?,
+            None => Value::Object(Map::new()),
+        };
+        let Value::Object(fields) = &mut json else {
+            let message = format!("{SETTINGS_FILE} is not a JSON object");
+            return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+        };
+        fields.insert(String::from("tutorial_done"), Value::Bool(true));
+        storage::save(SETTINGS_FILE, &json)
+    }
+}
+
+fn read<T: DeserializeOwned>(fields: &Map<String, Value>, name: &str, setting: &mut T) {
+    if let Some(value) = fields.get(name).and_then(|value| T::deserialize(value).ok()) {
+        *setting = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_bad_setting_keeps_the_others() {
+        let json = serde_json::json!({
+            "theme": "light",
+            "tutorial_done": true,
+            "cell_size": "huge",
+            "key_bindings": {"f1": "hlep", "ctrl+w": null},
+        });
+        let settings = Settings::from_json(&json);
+        assert!(settings.tutorial_done);
+        assert_eq!(settings.theme, ThemeName::Light);
+        assert!(settings.cell_size.is_none());
+        assert_eq!(settings.key_bindings.len(), 2);
+    }
+}
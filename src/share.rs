@@ -0,0 +1,43 @@
+//! The spoiler-free emoji grid printed after a game, for sharing results.
+
+use crate::{hints::Hint, App, CharacterState, GameState, Shade};
+
+pub fn grid(app: &App) -> String {
+    let score = match app.state {
+        GameState::Won => app.current_guess.to_string(),
+        _ => String::from("X"),
+    };
+    let mut lines = vec![format!("{} {score}/{}", app.title, app.playable_rows())];
+
+    let hints = app.hints().count();
+    if hints > 0 {
+        lines[0].push_str(&format!(" ({hints} hint{})", if hints == 1 { "" } else { "s" }));
+    }
+
+    lines.extend(app.guesses[..app.current_guess].iter().map(|row| {
+        row.char_states.iter().copied().map(emoji).collect::<String>()
+    }));
+    // Hints show their kind but not what they revealed.
+    lines.extend(app.hints().map(|hint| {
+        let kind = match hint {
+            Hint::Unmask { .. } => "unmasked a cell",
+            Hint::Letter { .. } => "tested a letter",
+            Hint::Position { .. } => "revealed a position",
+        };
+        format!("💡 {kind}")
+    }));
+    lines.join("\n")
+}
+
+fn emoji(state: CharacterState) -> char {
+    match state {
+        CharacterState::Correct => '🟩',
+        CharacterState::WrongPlace => '🟨',
+        CharacterState::NotInWord => '⬛',
+        CharacterState::InWord => '🟦',
+        CharacterState::LetterHidden(Shade::Correct) => '🟢',
+        CharacterState::LetterHidden(Shade::WrongPlace) => '🟡',
+        CharacterState::LetterHidden(Shade::NotInWord) => '⚫',
+        CharacterState::Masked | CharacterState::Delayed | CharacterState::Unknown => '⬜',
+    }
+}
@@ -0,0 +1,67 @@
+//! Signals that arrive while the terminal is in raw mode on the alternate
+//! screen. They are handled on a background thread, which leaves the game
+//! loop to notice them through flags.
+
+use crate::{enter_screen, leave_screen};
+use signal_hook::{
+    consts::{SIGCONT, SIGHUP, SIGSTOP, SIGTERM, SIGTSTP},
+    iterator::Signals,
+    low_level,
+};
+use std::{
+    io, process,
+    sync::atomic::{AtomicBool, AtomicI32, Ordering},
+    thread,
+    time::Duration,
+};
+
+/// How long the game loop gets to save and clean up after SIGTERM or SIGHUP
+/// before the process exits anyway. Modes that block on input never look.
+const SHUTDOWN_GRACE: Duration = Duration::from_millis(500);
+
+/// The signal that asked the process to quit, or 0.
+static TERMINATE: AtomicI32 = AtomicI32::new(0);
+static REDRAW: AtomicBool = AtomicBool::new(false);
+
+/// Starts handling SIGTERM, SIGHUP, SIGTSTP and SIGCONT.
+pub fn listen() -> io::Result<()> {
+    let mut signals = Signals::new([SIGTERM, SIGHUP, SIGTSTP, SIGCONT])?;
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            match signal {
+                SIGTSTP => suspend(),
+                SIGCONT => {
+                    let _ = enter_screen(&mut io::stdout());
+                    REDRAW.store(true, Ordering::SeqCst);
+                }
+                _ => {
+                    TERMINATE.store(signal, Ordering::SeqCst);
+                    thread::sleep(SHUTDOWN_GRACE);
+                    let _ = leave_screen(&mut io::stdout());
+                    process::exit(128 + signal);
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+/// The signal that asked the process to quit, if one did.
+pub fn terminating() -> Option<i32> {
+    Some(TERMINATE.load(Ordering::SeqCst)).filter(|signal| *signal != 0)
+}
+
+/// Whether the screen has to be drawn from scratch, after coming back from
+/// being suspended.
+pub fn take_redraw() -> bool {
+    REDRAW.swap(false, Ordering::SeqCst)
+}
+
+/// Gives the terminal back to the shell and stops the process, like ctrl-z
+/// does outside of raw mode. Picks the screen back up once continued.
+pub fn suspend() {
+    let _ = leave_screen(&mut io::stdout());
+    let _ = low_level::raise(SIGSTOP);
+    let _ = enter_screen(&mut io::stdout());
+    REDRAW.store(true, Ordering::SeqCst);
+}
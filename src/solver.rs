@@ -0,0 +1,54 @@
+use crate::{score_guess, CharacterState, Mask};
+
+/// Keeps track of the answers that are still consistent with the feedback seen
+/// so far and picks the next guess from them.
+pub struct Solver<'a> {
+    candidates: Vec<&'a str>,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(words: &[&'a str]) -> Self {
+        Self {
+            candidates: words.to_vec(),
+        }
+    }
+
+    /// Drops every candidate that would not have produced `char_states` when
+    /// `guess` was played on row `row_idx` of `mask`.
+    pub fn apply(
+        &mut self,
+        guess: &str,
+        char_states: &[CharacterState; 5],
+        mask: &Mask,
+        row_idx: usize,
+    ) {
+        self.candidates.retain(|candidate| {
+            *candidate != guess && score_guess(candidate, guess, mask, row_idx) == *char_states
+        });
+    }
+
+    /// Picks the candidate whose distinct letters show up in the most remaining
+    /// candidates, so each guess tests the most common letters first.
+    pub fn next_guess(&self) -> Option<&'a str> {
+        let mut letter_counts = [0usize; 26];
+        for candidate in &self.candidates {
+            for letter in distinct_letters(candidate) {
+                letter_counts[letter] += 1;
+            }
+        }
+
+        self.candidates.iter().copied().max_by_key(|candidate| {
+            distinct_letters(candidate)
+                .map(|letter| letter_counts[letter])
+                .sum::<usize>()
+        })
+    }
+}
+
+/// Yields the alphabet index of every letter in `word`, skipping repeats.
+fn distinct_letters(word: &str) -> impl Iterator<Item = usize> + '_ {
+    word.bytes()
+        .enumerate()
+        .filter(move |(idx, letter)| !word.as_bytes()[..*idx].contains(letter))
+        .map(|(_, letter)| (letter - b'a') as usize)
+}
@@ -0,0 +1,136 @@
+use crate::{
+    hints::Hint,
+    storage,
+    timer::{format_duration, TimerMode},
+    App, GameState, MAX_GUESSES,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, io, time::Duration};
+
+const STATS_FILE: &str = "stats.json";
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub played: u32,
+    pub won: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    /// `guess_distribution[n]` is the number of wins with `n + 1` guesses.
+    pub guess_distribution: [u32; MAX_GUESSES],
+    pub speedrun: SpeedrunStats,
+    pub hints: HintStats,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeedrunStats {
+    pub solves: u32,
+    pub best_ms: Option<u64>,
+    pub total_ms: u64,
+}
+
+/// How many of each kind of hint were bought, over all games.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HintStats {
+    pub games_with_hints: u32,
+    pub unmasked_cells: u32,
+    pub tested_letters: u32,
+    pub revealed_positions: u32,
+}
+
+impl HintStats {
+    fn total(&self) -> u32 {
+        self.unmasked_cells + self.tested_letters + self.revealed_positions
+    }
+}
+
+impl Stats {
+    pub fn load() -> Self {
+        storage::load(STATS_FILE)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        storage::save(STATS_FILE, self)
+    }
+
+    /// Adds a finished game to the totals.
+    pub fn record(&mut self, app: &App) {
+        self.played += 1;
+
+        match app.state {
+            GameState::Won => {
+                self.won += 1;
+                self.current_streak += 1;
+                self.max_streak = self.max_streak.max(self.current_streak);
+                self.guess_distribution[app.current_guess - 1] += 1;
+            }
+            GameState::Lost(_) => self.current_streak = 0,
+            GameState::InProgress => {}
+        }
+
+        for hint in app.hints() {
+            match hint {
+                Hint::Unmask { .. } => self.hints.unmasked_cells += 1,
+                Hint::Letter { .. } => self.hints.tested_letters += 1,
+                Hint::Position { .. } => self.hints.revealed_positions += 1,
+            }
+        }
+        if app.hints().next().is_some() {
+            self.hints.games_with_hints += 1;
+        }
+
+        if let Some(timer) = &app.timer {
+            if timer.mode == TimerMode::Speedrun && app.state == GameState::Won {
+                let solve_ms = timer.elapsed().as_millis() as u64;
+                self.speedrun.solves += 1;
+                self.speedrun.total_ms += solve_ms;
+                self.speedrun.best_ms = Some(match self.speedrun.best_ms {
+                    Some(best_ms) => best_ms.min(solve_ms),
+                    None => solve_ms,
+                });
+            }
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let win_rate = match self.played {
+            0 => 0.0,
+            played => self.won as f64 * 100.0 / played as f64,
+        };
+        writeln!(f, "Played:         {}", self.played)?;
+        writeln!(f, "Win rate:       {win_rate:.0}%")?;
+        writeln!(f, "Current streak: {}", self.current_streak)?;
+        writeln!(f, "Max streak:     {}", self.max_streak)?;
+        writeln!(f)?;
+        for (idx, wins) in self.guess_distribution.iter().enumerate() {
+            writeln!(f, "{} | {wins}", idx + 1)?;
+        }
+
+        if let Some(best_ms) = self.speedrun.best_ms {
+            let average_ms = self.speedrun.total_ms / u64::from(self.speedrun.solves.max(1));
+            writeln!(f)?;
+            writeln!(f, "Speedrun solves: {}", self.speedrun.solves)?;
+            writeln!(f, "Best time:       {}", format_duration(Duration::from_millis(best_ms)))?;
+            writeln!(f, "Average time:    {}", format_duration(Duration::from_millis(average_ms)))?;
+        }
+
+        if self.hints.total() > 0 {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "Hints used:      {} in {} games",
+                self.hints.total(),
+                self.hints.games_with_hints
+            )?;
+            writeln!(f, "Cells unmasked:  {}", self.hints.unmasked_cells)?;
+            writeln!(f, "Letters tested:  {}", self.hints.tested_letters)?;
+            writeln!(f, "Positions shown: {}", self.hints.revealed_positions)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+//! The line at the bottom of the screen with the keys that do something
+//! right now, and where the player is: mode, puzzle and guess.
+
+use crate::{keys::Action, App, GameState, Theme};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Below this size the line is given back to the board.
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 24;
+const SEPARATOR: &str = " · ";
+
+pub struct StatusBar {
+    /// Key and what it does, most useful first so the last ones are dropped
+    /// when the line is too narrow.
+    keys: Vec<(String, &'static str)>,
+    mode: &'static str,
+    info: Vec<String>,
+    /// How far into the board the player is, like `2/5`.
+    progress: Option<String>,
+}
+
+impl StatusBar {
+    pub fn new(mode: &'static str) -> Self {
+        Self {
+            keys: Vec::new(),
+            mode,
+            info: Vec::new(),
+            progress: None,
+        }
+    }
+
+    /// The keys of a single board played with [`crate::handle_key`].
+    pub fn for_game(app: &App) -> Self {
+        let mut bar = Self::new(app.mode);
+        let bound = |action| app.key_bindings.key_for(action).map(|key| key.to_string());
+
+        if app.show_help {
+            return bar.key("any key", "close");
+        }
+        if app.hint_menu.is_some() {
+            return bar.bound(bound(Action::Back), "cancel");
+        }
+
+        let rows = app.playable_rows();
+        if app.state == GameState::InProgress {
+            bar = bar
+                .key("a-z", "type")
+                .bound(bound(Action::Submit), "submit")
+                .bound(bound(Action::DeleteLetter), "delete");
+            if rows > app.current_guess + 1 {
+                bar = bar.bound(bound(Action::Hint), "hint");
+            }
+            bar = bar.bound(bound(Action::Help), "help");
+            bar.progress = Some(format!("{}/{rows}", app.current_guess + 1));
+        } else {
+            if app.has_masked_feedback() {
+                bar = bar.key("r", "reveal");
+            }
+            bar = bar.key("q", "quit");
+            bar.progress = Some(format!("{}/{rows}", app.current_guess));
+        }
+        bar.bound(bound(Action::Quit), "quit")
+    }
+
+    pub fn key(mut self, key: impl Into<String>, what: &'static str) -> Self {
+        let key = key.into();
+        if !self.keys.iter().any(|(_, other)| *other == what) {
+            self.keys.push((key, what));
+        }
+        self
+    }
+
+    /// Leaves out a key that a screen doesn't let through.
+    pub fn without(mut self, what: &str) -> Self {
+        self.keys.retain(|(_, other)| *other != what);
+        self
+    }
+
+    /// Adds `key` if the action has one, unbound actions are left out.
+    fn bound(self, key: Option<String>, what: &'static str) -> Self {
+        match key {
+            Some(key) => self.key(key, what),
+            None => self,
+        }
+    }
+
+    pub fn info(mut self, text: impl Into<String>) -> Self {
+        self.info.push(text.into());
+        self
+    }
+
+    pub fn progress(mut self, text: impl Into<String>) -> Self {
+        self.progress = Some(text.into());
+        self
+    }
+
+    fn right(&self) -> String {
+        std::iter::once(self.mode.to_string())
+            .chain(self.info.iter().cloned())
+            .chain(self.progress.clone())
+            .collect::<Vec<_>>()
+            .join(SEPARATOR)
+    }
+}
+
+/// Splits the status line off the bottom of `area`, unless the terminal is
+/// too small to spare it.
+pub fn split(area: Rect) -> (Rect, Option<Rect>) {
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        return (area, None);
+    }
+    let rest = Rect {
+        height: area.height - 1,
+        ..area
+    };
+    (rest, Some(Rect::new(area.x, area.bottom() - 1, area.width, 1)))
+}
+
+pub fn draw<B: Backend>(frame: &mut Frame<B>, theme: &Theme, bar: &StatusBar, area: Rect) {
+    let width = area.width as usize;
+    let right = bar.right();
+
+    // Keys are dropped from the end until they fit next to the info.
+    let mut keys = bar.keys.as_slice();
+    let keys_width = |keys: &[(String, &str)]| {
+        keys.iter()
+            .map(|(key, what)| key.chars().count() + what.chars().count() + 3)
+            .sum::<usize>()
+    };
+    while !keys.is_empty() && keys_width(keys) + right.chars().count() + 1 > width {
+        keys = &keys[..keys.len() - 1];
+    }
+
+    let style = Style::default().fg(theme.border_color);
+    let mut spans = Vec::new();
+    for (key, what) in keys {
+        spans.push(Span::styled(format!(" {key}"), style.add_modifier(Modifier::BOLD)));
+        spans.push(Span::styled(format!(" {what} "), style.add_modifier(Modifier::DIM)));
+    }
+    let padding = width.saturating_sub(keys_width(keys) + right.chars().count() + 1);
+    spans.push(Span::raw(" ".repeat(padding)));
+    spans.push(Span::styled(format!("{right} "), style));
+
+    frame.render_widget(Paragraph::new(Spans::from(spans)), area);
+}
@@ -0,0 +1,55 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Where saved data lives: `$SPOTLE_DATA_DIR` if set, otherwise
+/// `$XDG_DATA_HOME/spotle_tui`, falling back to `~/.local/share/spotle_tui`.
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("SPOTLE_DATA_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+
+    let base = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => Path::new(&env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(base.join("spotle_tui"))
+}
+
+/// Loads `file_name` from the data directory. A missing or unreadable file
+/// gives the default value, so a corrupt save never stops the game.
+pub fn load<T: DeserializeOwned + Default>(file_name: &str) -> T {
+    data_dir()
+        .and_then(|dir| fs::read_to_string(dir.join(file_name)).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// The contents of `file_name` in the data directory, or `None` if there is
+/// no such file yet.
+pub fn read(file_name: &str) -> io::Result<Option<String>> {
+    let dir = data_dir().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "could not find a data directory")
+    })?;
+    match fs::read_to_string(dir.join(file_name)) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Saves `value` to `file_name` in the data directory, replacing the old file
+/// only once the new one is fully written.
+pub fn save<T: Serialize>(file_name: &str, value: &T) -> io::Result<()> {
+    let dir = data_dir().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "could not find a data directory")
+    })?;
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(file_name);
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, serde_json::to_vec_pretty(value)?)?;
+    fs::rename(tmp_path, path)
+}
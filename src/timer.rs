@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// How the clock in the header behaves.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TimerMode {
+    /// Counts up from the first key press.
+    Stopwatch,
+    /// Counts up like `Stopwatch`, and a win records the time in the stats.
+    Speedrun,
+    /// The whole game has to be solved within this limit.
+    GameLimit(Duration),
+    /// Every guess has to be submitted within this limit.
+    GuessLimit(Duration),
+}
+
+pub struct Timer {
+    pub mode: TimerMode,
+    started: Option<Instant>,
+    guess_started: Option<Instant>,
+    stopped_after: Option<Duration>,
+    /// How long the last guess had taken when the clock stopped.
+    guess_stopped_after: Option<Duration>,
+    /// Stands in for the wall clock while a recording is played back.
+    played_now: Option<Instant>,
+}
+
+impl Timer {
+    pub fn new(mode: TimerMode) -> Self {
+        Self {
+            mode,
+            started: None,
+            guess_started: None,
+            stopped_after: None,
+            guess_stopped_after: None,
+            played_now: None,
+        }
+    }
+
+    /// Runs the clock on a recording's timestamps from now on, instead of
+    /// the wall clock.
+    pub fn set_now(&mut self, now: Instant) {
+        self.played_now = Some(now);
+    }
+
+    fn now(&self) -> Instant {
+        self.played_now.unwrap_or_else(Instant::now)
+    }
+
+    /// Starts the clock. Does nothing if it is already running.
+    pub fn start(&mut self) {
+        if self.started.is_none() {
+            let now = self.now();
+            self.started = Some(now);
+            self.guess_started = Some(now);
+        }
+    }
+
+    /// Restarts the per-guess limit after a guess was submitted.
+    pub fn next_guess(&mut self) {
+        if self.started.is_some() {
+            self.guess_started = Some(self.now());
+        }
+    }
+
+    /// Carries the clock on from a game picked back up, `elapsed` after its
+    /// first key press. The current guess gets its whole limit again.
+    pub fn resume_at(&mut self, elapsed: Duration) {
+        let now = self.now();
+        self.started = Some(now.checked_sub(elapsed).unwrap_or(now));
+        self.guess_started = Some(now);
+    }
+
+    pub fn stop(&mut self) {
+        if self.stopped_after.is_none() {
+            self.stopped_after = Some(self.elapsed());
+            self.guess_stopped_after = Some(self.guess_elapsed());
+        }
+    }
+
+    /// Time since the first key press, frozen once the game is over.
+    pub fn elapsed(&self) -> Duration {
+        match (self.stopped_after, self.started) {
+            (Some(elapsed), _) => elapsed,
+            (None, Some(started)) => self.now().saturating_duration_since(started),
+            (None, None) => Duration::ZERO,
+        }
+    }
+
+    /// Time spent on the current guess, frozen once the game is over.
+    fn guess_elapsed(&self) -> Duration {
+        match (self.guess_stopped_after, self.guess_started) {
+            (Some(elapsed), _) => elapsed,
+            (None, Some(guess_started)) => self.now().saturating_duration_since(guess_started),
+            (None, None) => Duration::ZERO,
+        }
+    }
+
+    /// Time left before the limit runs out, if this mode has one.
+    pub fn remaining(&self) -> Option<Duration> {
+        match self.mode {
+            TimerMode::GameLimit(limit) => Some(limit.saturating_sub(self.elapsed())),
+            TimerMode::GuessLimit(limit) => Some(limit.saturating_sub(self.guess_elapsed())),
+            TimerMode::Stopwatch | TimerMode::Speedrun => None,
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.stopped_after.is_none() && self.remaining() == Some(Duration::ZERO)
+    }
+
+    /// The text shown in the header while the game is running.
+    pub fn display(&self) -> String {
+        match self.remaining() {
+            Some(remaining) => format!("{} left", format_duration(remaining)),
+            None => format_duration(self.elapsed()),
+        }
+    }
+}
+
+/// Formats a duration as `m:ss.t`.
+pub fn format_duration(duration: Duration) -> String {
+    let tenths = duration.as_millis() / 100;
+    format!(
+        "{}:{:02}.{}",
+        tenths / 600,
+        (tenths / 10) % 60,
+        tenths % 10
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guess_limit_stays_run_out_once_stopped() {
+        let mut timer = Timer::new(TimerMode::GuessLimit(Duration::from_millis(10)));
+        timer.start();
+        std::thread::sleep(Duration::from_millis(20));
+        timer.stop();
+        assert_eq!(timer.remaining(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn played_back_clock_ignores_wall_time() {
+        let started = Instant::now();
+        let mut timer = Timer::new(TimerMode::GameLimit(Duration::from_secs(2)));
+        timer.set_now(started);
+        timer.start();
+        timer.set_now(started + Duration::from_secs(1));
+        assert_eq!(timer.remaining(), Some(Duration::from_secs(1)));
+        timer.set_now(started + Duration::from_secs(3));
+        assert!(timer.expired());
+    }
+}
@@ -0,0 +1,83 @@
+//! Short messages shown in the header one after another, each for a while
+//! depending on how much it matters.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Older messages are dropped beyond this many waiting.
+const MAX_QUEUED: usize = 4;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn duration(self) -> Duration {
+        match self {
+            Severity::Info | Severity::Success => Duration::from_millis(1500),
+            Severity::Warning | Severity::Error => Duration::from_millis(2500),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Toast {
+    pub text: String,
+    pub severity: Severity,
+    /// Set once the toast is first shown, its time runs from there.
+    shown_at: Option<Instant>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Queues a message. The same message twice in a row is shown once, for
+    /// as long as the last one asks.
+    pub fn push(&mut self, severity: Severity, text: impl Into<String>) {
+        let text = text.into();
+        if let Some(last) = self.queue.back_mut().filter(|last| last.text == text) {
+            last.shown_at = None;
+            return;
+        }
+        if self.queue.len() == MAX_QUEUED {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(Toast {
+            text,
+            severity,
+            shown_at: None,
+        });
+    }
+
+    /// The message to show now, after dropping the ones whose time is up.
+    pub fn current(&mut self) -> Option<&Toast> {
+        let now = Instant::now();
+        while let Some(toast) = self.queue.front_mut() {
+            match toast.shown_at {
+                Some(shown_at) if now - shown_at >= toast.severity.duration() => {
+                    self.queue.pop_front();
+                }
+                Some(_) => break,
+                None => {
+                    toast.shown_at = Some(now);
+                    break;
+                }
+            }
+        }
+        self.queue.front()
+    }
+
+    /// Takes every waiting message at once, for output that isn't redrawn.
+    pub fn drain(&mut self) -> impl Iterator<Item = Toast> + '_ {
+        self.queue.drain(..)
+    }
+}
@@ -0,0 +1,140 @@
+//! A guided first game: a fixed puzzle where every step says which word to
+//! type next and explains the feedback it got.
+
+use crate::{
+    draw_game, handle_key,
+    keys::{Action, KeyBindings},
+    read_event,
+    status_bar::{self, StatusBar},
+    App, GameState, Mask,
+};
+use crossterm::event::{Event, KeyCode};
+use std::io;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::{Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+const ANSWER: &str = "spark";
+const MASK: &str = "..#../...?./...../...../.....";
+const INSTRUCTION_HEIGHT: u16 = 3;
+
+/// The guess to type at each step, and what to say before it.
+const STEPS: [(&str, &str); 3] = [
+    (
+        "crane",
+        "Welcome to Spotle! Find the hidden five letter word. \
+         Start by typing CRANE and pressing Enter.",
+    ),
+    (
+        "shark",
+        "Green is the right letter in the right place, yellow is in the word \
+         elsewhere and grey is not in it. The reversed cell was masked, so the \
+         A gave nothing away. Now type SHARK.",
+    ),
+    (
+        "spark",
+        "The rounded cell only tells whether its letter is in the word at all: \
+         R is, but not where. Try SPARK to win.",
+    ),
+];
+
+/// What to do once the tutorial is solved, naming the keys the player has
+/// bound.
+fn done(key_bindings: &KeyBindings) -> String {
+    let mut tips = Vec::new();
+    if let Some(key) = key_bindings.key_for(Action::Help) {
+        tips.push(format!("{key} for help at any time"));
+    }
+    if let Some(key) = key_bindings.key_for(Action::Hint) {
+        tips.push(format!("{key} to buy a hint with your last row"));
+    }
+    let mut text = String::from("Solved!");
+    if !tips.is_empty() {
+        text.push_str(&format!(" Press {}.", tips.join(", and ")));
+    }
+    let start = key_bindings
+        .key_for(Action::Submit)
+        .map_or(String::from("q"), |key| key.to_string());
+    text + &format!(" Press {start} to start a real game.")
+}
+
+/// Plays the tutorial until it is solved or skipped with esc. Returns whether
+/// the player quit, rather than finishing or skipping it.
+pub fn run<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<bool> {
+    let mask = Mask::parse(MASK).expect("tutorial mask is valid");
+    let mut app = App {
+        title: String::from("Spotle Tui - Tutorial"),
+        mode: "Tutorial",
+        ..App::new(ANSWER, mask)
+    };
+
+    loop {
+        terminal.draw(|f| ui(f, &mut app))?;
+
+        let Event::Key(key) = read_event(terminal, &app.key_bindings)? else {
+            continue;
+        };
+        let action = app.key_bindings.action(key);
+        if app.state != GameState::InProgress {
+            if key.code == KeyCode::Char('q')
+                || matches!(action, Some(Action::Submit | Action::Back | Action::Quit))
+            {
+                return Ok(action == Some(Action::Quit));
+            }
+            continue;
+        }
+
+        // Only let through keys that keep to the word of this step.
+        let expected = STEPS[app.current_guess].0;
+        let allowed = match (action, key.code) {
+            (None, KeyCode::Char(c)) if !app.show_help => {
+                expected.starts_with(&format!("{}{}", app.input, c.to_ascii_lowercase()))
+            }
+            (Some(Action::Submit), _) => app.input == expected,
+            (Some(Action::Hint), _) => false,
+            _ => true,
+        };
+        if allowed && handle_key(&mut app, key).is_break() {
+            return Ok(action == Some(Action::Quit));
+        }
+    }
+}
+
+fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App) {
+    let (area, status_area) = status_bar::split(frame.size());
+    let game_area = Rect {
+        height: area.height.saturating_sub(INSTRUCTION_HEIGHT),
+        ..area
+    };
+    let instruction = match app.state {
+        GameState::InProgress => STEPS[app.current_guess].1.to_string(),
+        _ => done(&app.key_bindings),
+    };
+    draw_game(frame, app, game_area);
+
+    let instruction = Paragraph::new(instruction)
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center)
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    frame.render_widget(
+        instruction,
+        Rect::new(
+            area.x,
+            area.y + game_area.height,
+            area.width,
+            area.height - game_area.height,
+        ),
+    );
+
+    if let Some(status_area) = status_area {
+        let bar = match app.state {
+            GameState::InProgress => StatusBar::for_game(app).without("hint"),
+            _ => StatusBar::new(app.mode).key("enter", "start a game"),
+        };
+        status_bar::draw(frame, &app.theme, &bar, status_area);
+    }
+}